@@ -0,0 +1,33 @@
+//! Compares `semtag get` on a repository with many tags with and without `--max-tags`
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{stdout, TestRepo};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const TAGS: i64 = 5_000;
+
+fn repo_with_many_tags() -> TestRepo {
+    let repo = TestRepo::new();
+    for index in 0..TAGS {
+        let tag = format!("{}.{}.{}", index / 1_000, index / 100 % 10, index % 100);
+        repo.tag_at(&tag, 1_700_000_000 + index);
+    }
+    repo
+}
+
+fn get_latest(c: &mut Criterion) {
+    let repo = repo_with_many_tags();
+    let mut group = c.benchmark_group("get");
+    group.sample_size(20);
+
+    group.bench_function("all tags", |b| b.iter(|| stdout(repo.semtag().arg("get"))));
+    group.bench_function("--max-tags 100", |b| {
+        b.iter(|| stdout(repo.semtag().args(["get", "--max-tags", "100"])))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, get_latest);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+use git2::Repository;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The last change to a directory of loose refs or any directory nested in it
+fn dir_modified(path: &Path) -> Option<SystemTime> {
+    let nested = fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter_map(|entry| dir_modified(&entry.path()));
+
+    nested.fold(modified(path), |latest, nested| latest.max(Some(nested)))
+}
+
+/// The last change to the tags, `packed-refs` changes whenever tags are packed or a packed tag is
+/// deleted, a directory below `refs/tags` whenever a loose tag in it is created or deleted
+fn refs_modified(repo: &Repository) -> Option<SystemTime> {
+    let git_dir = repo.commondir();
+    let packed_refs = modified(&git_dir.join("packed-refs"));
+    let loose_refs = dir_modified(&git_dir.join("refs").join("tags"));
+
+    packed_refs.max(loose_refs)
+}
+
+/// Reads the cached tag names, `None` when the cache is missing, unreadable, or older than the
+/// refs
+pub fn load(repo: &Repository, path: &Path) -> Option<Vec<String>> {
+    let cache_modified = modified(path)?;
+    if refs_modified(repo).is_some_and(|refs_modified| refs_modified >= cache_modified) {
+        return None;
+    }
+
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Scans the names of all tags of the repository
+pub fn scan(repo: &Repository) -> Result<Vec<String>, git2::Error> {
+    let names = repo.tag_names(None)?;
+    Ok(names
+        .iter()
+        .flatten()
+        .map(|name| name.to_string())
+        .collect())
+}
+
+pub fn store(path: &Path, tags: &[String]) -> Result<(), String> {
+    let content = serde_json::to_string(tags)
+        .map_err(|e| format!("Failed to serialize the tag cache: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+}
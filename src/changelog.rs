@@ -0,0 +1,20 @@
+use crate::git::CommitSummary;
+
+/// Renders the changelog section of a release, e.g.
+///
+/// ```markdown
+/// ## [1.3.0] - 2024-05-01
+///
+/// - Add the --pattern option (2b8e1f0)
+/// ```
+pub fn render_entry(tag: &str, date: &str, commits: &[CommitSummary]) -> String {
+    let mut entry = format!("## [{}] - {}\n\n", tag, date);
+    if commits.is_empty() {
+        entry.push_str("- No changes\n");
+    }
+    for commit in commits {
+        entry.push_str(&format!("- {} ({})\n", commit.summary, commit.short_id));
+    }
+
+    entry
+}
@@ -0,0 +1,705 @@
+use clap::builder::{BoolishValueParser, PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use glob::Pattern;
+use semtag::{
+    normalize_scope, Version, OPT_ALPHA, OPT_BETA, OPT_RC, SCOPE_ALIASES, SCOPE_AUTO, SCOPE_MAJOR,
+    SCOPE_MINOR, SCOPE_NONE, SCOPE_PATCH,
+};
+use std::path::PathBuf;
+
+use crate::git::DEFAULT_TAG_REF_FORMAT;
+
+#[derive(Parser, Debug)]
+#[command(
+    version,
+    arg_required_else_help = true,
+    args_conflicts_with_subcommands = true
+)]
+/// A CLI app to bump semver tag
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub bump: BumpArgs,
+    #[command(flatten)]
+    pub global: GlobalArgs,
+}
+
+#[derive(Args, Default, Debug)]
+pub struct GlobalArgs {
+    #[arg(short = 'v', long, global = true, env = "SEMTAG_VERBOSE", action = ArgAction::Count)]
+    /// Print debug output to stderr, repeat for more detail (-vv)
+    pub verbose: u8,
+    #[arg(
+        short = 'C',
+        long,
+        env = "SEMTAG_PATH",
+        visible_alias = "repo-path",
+        global = true
+    )]
+    /// Run as if semtag was started in the given path instead of the current directory
+    pub path: Option<PathBuf>,
+    #[arg(long, env = "SEMTAG_GIT_DIR", global = true)]
+    /// The path to the .git directory, for worktrees outside the repository
+    pub git_dir: Option<PathBuf>,
+    #[arg(
+        long,
+        env = "SEMTAG_COLOR",
+        global = true,
+        value_name = "WHEN",
+        value_enum,
+        default_value_t = ColorChoice::Auto
+    )]
+    /// When to use colored output, auto only colors a terminal
+    pub color: ColorChoice,
+    #[arg(
+        long,
+        env = "SEMTAG_NO_COLOR",
+        value_parser = BoolishValueParser::new(),
+        global = true,
+        action
+    )]
+    /// Disable colored output, like setting the NO_COLOR environment variable or --color never
+    pub no_color: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum TagSort {
+    #[default]
+    Version,
+    Created,
+}
+
+#[derive(ValueEnum, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Bump the latest version and create a new tag
+    Bump(Box<BumpArgs>),
+    /// Print the latest version tag
+    #[command(visible_alias = "current")]
+    Get(GetArgs),
+    /// List the version tags, lowest version first
+    List(ListArgs),
+    /// Delete a tag
+    Delete(DeleteArgs),
+    /// Explain the change between two versions
+    Diff(DiffArgs),
+    /// Print -1, 0, or 1 when the first version is lower, equal, or higher than the second
+    Compare(CompareArgs),
+}
+
+#[derive(Args, Clone, Default, Debug)]
+pub struct BumpArgs {
+    #[arg(short = 's', long, env = "SEMTAG_SCOPE", value_parser = scope_parser())]
+    /// The scope of the version: major, minor, patch, none to only apply --option, or auto to detect it from Conventional Commits, aliases such as feat or fix are accepted
+    pub scope: Option<String>,
+    #[arg(short = 'o', long, env = "SEMTAG_OPTION")]
+    /// The option to be used: alpha, beta, rc, or just left it empty
+    pub option: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_ALLOWED_OPTIONS",
+        value_name = "OPTIONS",
+        value_delimiter = ',',
+        default_values = [OPT_ALPHA, OPT_BETA, OPT_RC]
+    )]
+    /// The comma-separated pre-release options accepted by --option, from the lowest stage up
+    pub allowed_options: Vec<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_PRERELEASE_ONLY",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with_all = ["scope", "option"]
+    )]
+    /// Only increment the pre-release counter of the latest version, e.g. 1.2.0-rc.1 to 1.2.0-rc.2
+    pub prerelease_only: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_STRICT_PRERELEASE_ORDER",
+        value_parser = BoolishValueParser::new(),
+        action
+    )]
+    /// Refuse to go back a pre-release stage within the same version, e.g. from beta to alpha
+    pub strict_prerelease_order: bool,
+    #[arg(long, env = "SEMTAG_RC_NUMBER", value_name = "N")]
+    /// Start the rc series at this number instead of incrementing it, requires --option rc
+    pub rc_number: Option<u32>,
+    #[arg(long, env = "SEMTAG_MIN_VERSION", value_name = "VERSION", value_parser = Version::parse)]
+    /// Use this version instead when the computed one is lower, e.g. 2.0.0 after a major rewrite
+    pub min_version: Option<Version>,
+    #[arg(long, env = "SEMTAG_BUILD", value_name = "META")]
+    /// Append build metadata to the new version, e.g. ci.42 for 1.2.0+ci.42
+    pub build: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_BUILD_TIMESTAMP",
+        value_parser = BoolishValueParser::new(),
+        action
+    )]
+    /// Append the current UTC time as build metadata, e.g. 1.2.0+20240615T101500Z, after --build if both are given
+    pub build_timestamp: bool,
+    #[arg(long, env = "SEMTAG_BASE_TAG", value_name = "TAG")]
+    /// Bump this tag instead of the latest one, e.g. to release a hotfix off an older series
+    pub base_tag: Option<String>,
+    #[arg(short = 'p', long, env = "SEMTAG_PREFIX")]
+    /// The prefix to be used: prod, stage, sandbox, dev, etc
+    pub prefix: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_PATTERN",
+        value_name = "GLOB",
+        conflicts_with = "prefix"
+    )]
+    /// Only load the tags matching the glob, e.g. "release-*", --prefix is a shorthand for "<PREFIX>-*"
+    pub pattern: Option<String>,
+    #[arg(long, env = "SEMTAG_NAMESPACE", value_parser = parse_namespace)]
+    /// Keep the tags under refs/tags/<NAMESPACE>/, e.g. releases
+    pub namespace: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_TAG_REF_FORMAT",
+        value_name = "FORMAT",
+        value_parser = parse_tag_ref_format,
+        default_value = DEFAULT_TAG_REF_FORMAT,
+        conflicts_with = "namespace"
+    )]
+    /// The ref the tags live under, e.g. "refs/tags/releases/{name}"
+    pub tag_ref_format: String,
+    #[arg(
+        short = 'd',
+        long,
+        env = "SEMTAG_DRY_RUN",
+        value_parser = BoolishValueParser::new(),
+        action
+    )]
+    /// Dry run mode, do not create a tag
+    pub dry_run: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_NO_TAG",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with = "dry_run"
+    )]
+    /// Run the bump without creating or pushing the tag
+    pub no_tag: bool,
+    #[arg(long, env = "SEMTAG_FORMAT", value_enum, default_value_t = Format::Text)]
+    /// The output format, json and github-actions send status messages to stderr
+    pub format: Format,
+    #[arg(long, env = "SEMTAG_CHANGELOG_ENTRY", value_parser = BoolishValueParser::new(), action)]
+    /// Print the changelog entry of the new version to stdout instead of creating a tag
+    pub changelog_entry: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_SUMMARY",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with = "changelog_entry"
+    )]
+    /// Print a one-line summary of the bump to stdout instead of creating a tag
+    pub summary: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_SHOW_NEXT",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with_all = ["scope", "option", "prerelease_only", "summary", "changelog_entry"]
+    )]
+    /// Print the next version for every scope and option instead of bumping
+    pub show_next: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_CONFIG_SHOW",
+        value_parser = BoolishValueParser::new(),
+        action
+    )]
+    /// Print the settings resolved from the flags, environment variables, .semtag.toml, and git config as TOML, each with its source, then exit
+    pub config_show: bool,
+    #[arg(
+        short = 'a',
+        long,
+        env = "SEMTAG_ANNOTATE",
+        value_parser = BoolishValueParser::new(),
+        visible_alias = "annotated",
+        action
+    )]
+    /// Create an annotated tag
+    pub annotate: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_ANNOTATED_ONLY",
+        value_parser = BoolishValueParser::new(),
+        action
+    )]
+    /// Only use annotated tags as the base version, ignoring lightweight ones
+    pub annotated_only: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_LIGHTWEIGHT",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with_all = [
+            "annotate",
+            "message",
+            "message_template",
+            "tag_body_file",
+            "annotate_from_changelog",
+            "tag_message_format",
+            "template_file",
+            "sign"
+        ]
+    )]
+    /// Create a lightweight tag even if the semtag.annotate git config is true
+    pub lightweight: bool,
+    #[arg(short = 'm', long, env = "SEMTAG_MESSAGE")]
+    /// The message of the annotated tag
+    pub message: Option<String>,
+    #[arg(long, env = "SEMTAG_MESSAGE_TEMPLATE", conflicts_with = "message")]
+    /// Render the annotated tag message from a template, e.g. "Release {version}"
+    pub message_template: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_TAG_BODY_FILE",
+        value_name = "PATH",
+        conflicts_with_all = ["message", "message_template"]
+    )]
+    /// Read the annotated tag message from a file, e.g. release notes
+    pub tag_body_file: Option<PathBuf>,
+    #[arg(
+        long,
+        env = "SEMTAG_ANNOTATE_FROM_CHANGELOG",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with_all = ["message", "message_template", "tag_body_file"]
+    )]
+    /// Use the changelog entry of the new version as the annotated tag message, implies --annotate
+    pub annotate_from_changelog: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_TAG_MESSAGE_FORMAT",
+        value_name = "TEMPLATE",
+        conflicts_with_all = ["message", "message_template", "tag_body_file", "annotate_from_changelog"]
+    )]
+    /// Render the annotated tag message from the commits since the latest tag, e.g. "{scope}: {commits}", implies --annotate
+    pub tag_message_format: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_TEMPLATE_FILE",
+        value_name = "PATH",
+        conflicts_with_all = [
+            "message",
+            "message_template",
+            "tag_body_file",
+            "annotate_from_changelog",
+            "tag_message_format"
+        ]
+    )]
+    /// Render the annotated tag message from a template file with {version}, {previous}, and {date} placeholders, implies --annotate
+    pub template_file: Option<PathBuf>,
+    #[arg(
+        short = 'S',
+        long,
+        env = "SEMTAG_SIGN",
+        value_name = "KEY_ID",
+        num_args = 0..=1,
+        default_missing_value = ""
+    )]
+    /// Create a gpg-signed annotated tag, with the default key unless a key ID is given
+    pub sign: Option<String>,
+    #[arg(long, env = "SEMTAG_GPG_PROGRAM", requires = "sign")]
+    /// The gpg program used for signing, defaults to gpg2 then gpg
+    pub gpg_program: Option<PathBuf>,
+    #[arg(long, env = "SEMTAG_CREATE_BRANCH", value_name = "NAME")]
+    /// Also create a branch on the tagged commit, e.g. "release/{major}.{minor}"
+    pub create_branch: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_FORCE",
+        value_parser = BoolishValueParser::new(),
+        action,
+        requires = "create_branch"
+    )]
+    /// Overwrite the branch of --create-branch if it already exists
+    pub force: bool,
+    #[arg(long, env = "SEMTAG_PRE_RUN_HOOK", value_name = "CMD")]
+    /// Run a shell command before creating the tag, a failure aborts the tag creation
+    pub pre_run_hook: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_PRE_PUSH_HOOK",
+        value_name = "CMD",
+        requires = "push"
+    )]
+    /// Run a shell command before pushing the tag, a failure keeps the tag local
+    pub pre_push_hook: Option<String>,
+    #[arg(long, env = "SEMTAG_POST_RUN_HOOK", value_name = "CMD")]
+    /// Run a shell command after the tag is created, a failure only prints a warning
+    pub post_run_hook: Option<String>,
+    #[arg(long, env = "SEMTAG_POST_TAG_COMMAND", value_name = "CMD")]
+    /// Run a shell command after the tag is created, with the tag in SEMTAG_NEW_TAG, its exit code becomes semtag's
+    pub post_tag_command: Option<String>,
+    #[arg(long, env = "SEMTAG_FETCH", value_parser = BoolishValueParser::new(), action)]
+    /// Fetch the tags from the remote before looking up the latest one
+    pub fetch: bool,
+    #[arg(long, env = "SEMTAG_PUSH", value_parser = BoolishValueParser::new(), action)]
+    /// Push the new tag to the remote
+    pub push: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_REMOTE",
+        value_name = "NAME",
+        default_value = "origin"
+    )]
+    /// The remote used by --fetch and --push
+    pub remote: String,
+    #[arg(long, env = "SEMTAG_RETRIES", value_name = "N", default_value_t = 3)]
+    /// Retry --fetch and --push this many times after a network failure, with exponential backoff
+    pub retries: u32,
+    #[arg(
+        long,
+        env = "SEMTAG_UPDATE_CARGO_VERSION",
+        value_parser = BoolishValueParser::new(),
+        action
+    )]
+    /// Also set the new version, without the prefix, in the Cargo manifest
+    pub update_cargo_version: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_CARGO_MANIFEST",
+        value_name = "PATH",
+        default_value = "Cargo.toml"
+    )]
+    /// The Cargo manifest updated by --update-cargo-version, the workspace root with --workspace
+    pub cargo_manifest: PathBuf,
+    #[arg(
+        long,
+        env = "SEMTAG_WORKSPACE",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with_all = ["prefix", "pattern", "base_tag"]
+    )]
+    /// Bump every crate of the Cargo workspace, using the crate name as the prefix
+    pub workspace: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_UPDATE_PACKAGE_JSON",
+        value_parser = BoolishValueParser::new(),
+        action
+    )]
+    /// Also set the new version, without the prefix, in package.json
+    pub update_package_json: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_PACKAGE_JSON",
+        value_name = "PATH",
+        default_value = "package.json"
+    )]
+    /// The package.json updated by --update-package-json
+    pub package_json: PathBuf,
+    #[arg(long, env = "SEMTAG_UPDATE_VERSION_FILE", value_name = "PATH")]
+    /// Also write the new version, without the prefix, to a plain text file such as VERSION
+    pub update_version_file: Option<PathBuf>,
+    #[arg(long, env = "SEMTAG_NO_LOCK", value_parser = BoolishValueParser::new(), action)]
+    /// Do not record the version transition in semtag.lock
+    pub no_lock: bool,
+    #[arg(long, env = "SEMTAG_STAGE", value_parser = BoolishValueParser::new(), action)]
+    /// Stage the updated version files in the git index
+    pub stage: bool,
+    #[arg(long, env = "SEMTAG_REQUIRE_COMMITS", value_parser = BoolishValueParser::new(), action)]
+    /// Refuse to bump when there are no new commits since the latest tag
+    pub require_commits: bool,
+    #[arg(long, env = "SEMTAG_ONLY_IF_CHANGED", value_name = "PATH")]
+    /// Exit without tagging when no file under the path, relative to the repository root, changed since the latest tag
+    pub only_if_changed: Option<PathBuf>,
+    #[arg(long, env = "SEMTAG_FIRST_PARENT", value_parser = BoolishValueParser::new(), action)]
+    /// Only follow the first parent of merge commits when looking at the commits since the latest tag
+    pub first_parent: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_SKIP_IF_RELEASE_COMMIT",
+        value_parser = BoolishValueParser::new(),
+        action
+    )]
+    /// Exit without tagging when the HEAD commit is a release commit
+    pub skip_if_release_commit: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_RELEASE_COMMIT_PATTERN",
+        value_name = "PATTERN",
+        default_value = "chore(release):"
+    )]
+    /// The commit message prefix that marks a release commit for --skip-if-release-commit
+    pub release_commit_pattern: String,
+    #[arg(long, env = "SEMTAG_STRICT", value_parser = BoolishValueParser::new(), action)]
+    /// Treat warnings as errors
+    pub strict: bool,
+    #[arg(long, env = "SEMTAG_ALLOW_DOWNGRADE", value_parser = BoolishValueParser::new(), action)]
+    /// Tag even when an equal or higher version already exists, e.g. a patch release of an older series
+    pub allow_downgrade: bool,
+    #[arg(short = 'i', long, env = "SEMTAG_INCLUDE", value_parser = parse_pattern)]
+    /// Only consider tags matching the glob pattern, can be repeated
+    pub include: Vec<Pattern>,
+    #[arg(short = 'x', long, env = "SEMTAG_EXCLUDE", value_parser = parse_pattern)]
+    /// Ignore tags matching the glob pattern, can be repeated
+    pub exclude: Vec<Pattern>,
+    #[arg(long, env = "SEMTAG_MAX_TAGS", value_name = "N")]
+    /// Only consider the N most recently created tags, for repositories with many tags
+    pub max_tags: Option<usize>,
+    #[arg(long, env = "SEMTAG_TAG_SORT", value_enum, default_value_t = TagSort::Version)]
+    /// Pick the latest tag by version or by creation time, e.g. for hotfixes published out of order
+    pub tag_sort: TagSort,
+    #[arg(long, env = "SEMTAG_CACHE_FILE", value_name = "PATH")]
+    /// Cache the tag list in this file to speed up repeated runs on repositories with many tags
+    pub cache_file: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    /// Appends previous, next, and created to $GITHUB_OUTPUT
+    GithubActions,
+}
+
+#[derive(Args, Default, Debug)]
+pub struct GetArgs {
+    #[arg(short = 'p', long, env = "SEMTAG_PREFIX")]
+    /// The prefix to be used: prod, stage, sandbox, dev, etc
+    pub prefix: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_PATTERN",
+        value_name = "GLOB",
+        conflicts_with = "prefix"
+    )]
+    /// Only load the tags matching the glob, e.g. "release-*", --prefix is a shorthand for "<PREFIX>-*"
+    pub pattern: Option<String>,
+    #[arg(long, env = "SEMTAG_NAMESPACE", value_parser = parse_namespace)]
+    /// Keep the tags under refs/tags/<NAMESPACE>/, e.g. releases
+    pub namespace: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_TAG_REF_FORMAT",
+        value_name = "FORMAT",
+        value_parser = parse_tag_ref_format,
+        default_value = DEFAULT_TAG_REF_FORMAT,
+        conflicts_with = "namespace"
+    )]
+    /// The ref the tags live under, e.g. "refs/tags/releases/{name}"
+    pub tag_ref_format: String,
+    #[arg(short = 'i', long, env = "SEMTAG_INCLUDE", value_parser = parse_pattern)]
+    /// Only consider tags matching the glob pattern, can be repeated
+    pub include: Vec<Pattern>,
+    #[arg(short = 'x', long, env = "SEMTAG_EXCLUDE", value_parser = parse_pattern)]
+    /// Ignore tags matching the glob pattern, can be repeated
+    pub exclude: Vec<Pattern>,
+    #[arg(long, env = "SEMTAG_MAX_TAGS", value_name = "N")]
+    /// Only consider the N most recently created tags, for repositories with many tags
+    pub max_tags: Option<usize>,
+    #[arg(long, env = "SEMTAG_TAG_SORT", value_enum, default_value_t = TagSort::Version)]
+    /// Pick the latest tag by version or by creation time, e.g. for hotfixes published out of order
+    pub tag_sort: TagSort,
+    #[arg(long, env = "SEMTAG_CACHE_FILE", value_name = "PATH")]
+    /// Cache the tag list in this file to speed up repeated runs on repositories with many tags
+    pub cache_file: Option<PathBuf>,
+    #[arg(
+        long,
+        env = "SEMTAG_WORKSPACE",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with_all = ["prefix", "pattern"]
+    )]
+    /// Print the latest tag of every crate of the Cargo workspace, using the crate name as the prefix
+    pub workspace: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_CARGO_MANIFEST",
+        value_name = "PATH",
+        default_value = "Cargo.toml",
+        requires = "workspace"
+    )]
+    /// The Cargo manifest at the root of the workspace
+    pub cargo_manifest: PathBuf,
+    #[arg(long, env = "SEMTAG_LATEST_STABLE", value_parser = BoolishValueParser::new(), action)]
+    /// Print the latest tag without a pre-release label, e.g. for deployment pipelines
+    pub latest_stable: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_LATEST_PRERELEASE",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with = "latest_stable"
+    )]
+    /// Print the latest tag with a pre-release label, e.g. the rc to promote to production
+    pub latest_prerelease: bool,
+}
+
+#[derive(Args, Default, Debug)]
+pub struct ListArgs {
+    #[arg(short = 'p', long, env = "SEMTAG_PREFIX")]
+    /// The prefix to be used: prod, stage, sandbox, dev, etc
+    pub prefix: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_PATTERN",
+        value_name = "GLOB",
+        conflicts_with = "prefix"
+    )]
+    /// Only load the tags matching the glob, e.g. "release-*", --prefix is a shorthand for "<PREFIX>-*"
+    pub pattern: Option<String>,
+    #[arg(long, env = "SEMTAG_NAMESPACE", value_parser = parse_namespace)]
+    /// Keep the tags under refs/tags/<NAMESPACE>/, e.g. releases
+    pub namespace: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_TAG_REF_FORMAT",
+        value_name = "FORMAT",
+        value_parser = parse_tag_ref_format,
+        default_value = DEFAULT_TAG_REF_FORMAT,
+        conflicts_with = "namespace"
+    )]
+    /// The ref the tags live under, e.g. "refs/tags/releases/{name}"
+    pub tag_ref_format: String,
+    #[arg(short = 'i', long, env = "SEMTAG_INCLUDE", value_parser = parse_pattern)]
+    /// Only consider tags matching the glob pattern, can be repeated
+    pub include: Vec<Pattern>,
+    #[arg(short = 'x', long, env = "SEMTAG_EXCLUDE", value_parser = parse_pattern)]
+    /// Ignore tags matching the glob pattern, can be repeated
+    pub exclude: Vec<Pattern>,
+    #[arg(long, env = "SEMTAG_TAG_SORT", value_enum, default_value_t = TagSort::Version)]
+    /// Pick the latest tag by version or by creation time, e.g. for hotfixes published out of order
+    pub tag_sort: TagSort,
+    #[arg(long, env = "SEMTAG_CACHE_FILE", value_name = "PATH")]
+    /// Cache the tag list in this file to speed up repeated runs on repositories with many tags
+    pub cache_file: Option<PathBuf>,
+    #[arg(long, env = "SEMTAG_RECENT", value_name = "N")]
+    /// Only print the N latest version tags, still lowest version first
+    pub recent: Option<usize>,
+    #[arg(long, env = "SEMTAG_JSON", value_parser = BoolishValueParser::new(), action)]
+    /// Print a JSON array with the version, commit, and tagger date of each tag
+    pub json: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_LIST_FORMAT",
+        value_name = "FMT",
+        conflicts_with = "json"
+    )]
+    /// Print each tag in this format: %n name, %v version, %p prefix, %c commit, %d date, %a tagger
+    pub format: Option<String>,
+    #[arg(
+        long,
+        env = "SEMTAG_LIST_REMOTE",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with_all = ["json", "format", "cache_file"]
+    )]
+    /// Print the version tags that exist on the remote but not locally, without fetching them
+    pub list_remote: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_REMOTE",
+        value_name = "NAME",
+        default_value = "origin"
+    )]
+    /// The remote used by --list-remote
+    pub remote: String,
+    #[arg(long, env = "SEMTAG_RETRIES", value_name = "N", default_value_t = 3)]
+    /// Retry --list-remote this many times after a network failure, with exponential backoff
+    pub retries: u32,
+}
+
+#[derive(Args, Default, Debug)]
+pub struct DeleteArgs {
+    /// The tag to delete
+    pub tag: String,
+    #[arg(
+        short = 'd',
+        long,
+        env = "SEMTAG_DRY_RUN",
+        value_parser = BoolishValueParser::new(),
+        action
+    )]
+    /// Dry run mode, do not delete the tag
+    pub dry_run: bool,
+}
+
+#[derive(Args, Default, Debug)]
+pub struct DiffArgs {
+    /// The version to compare from, e.g. 1.2.3
+    pub from: String,
+    /// The version to compare to, e.g. 1.3.0
+    pub to: String,
+    #[arg(long, env = "SEMTAG_DISTANCE", value_parser = BoolishValueParser::new(), action)]
+    /// Print the difference per component, e.g. "major +1, minor -4, patch -2"
+    pub distance: bool,
+    #[arg(
+        long,
+        env = "SEMTAG_DIFF_ONLY",
+        value_parser = BoolishValueParser::new(),
+        action,
+        conflicts_with = "distance"
+    )]
+    /// Treat the versions as tags and print the files changed, insertions, and deletions between their commits
+    pub diff_only: bool,
+}
+
+#[derive(Args, Default, Debug)]
+pub struct CompareArgs {
+    /// The version on the left-hand side, e.g. 1.3.0-rc.1
+    pub a: String,
+    /// The version on the right-hand side, e.g. 1.3.0
+    pub b: String,
+}
+
+/// Accepts the scopes and their aliases, which are not listed in the help, and yields the
+/// canonical scope, so that `-s feat` is the same as `-s minor`. `auto` is resolved later from
+/// the commits.
+fn scope_parser() -> impl TypedValueParser<Value = String> {
+    let scopes = [
+        SCOPE_MAJOR,
+        SCOPE_MINOR,
+        SCOPE_PATCH,
+        SCOPE_NONE,
+        SCOPE_AUTO,
+    ]
+    .map(|scope| {
+        let aliases = SCOPE_ALIASES
+            .iter()
+            .filter(move |(_, canonical)| *canonical == scope)
+            .map(|(alias, _)| *alias);
+        PossibleValue::new(scope).aliases(aliases)
+    });
+
+    PossibleValuesParser::new(scopes)
+        .map(|scope| normalize_scope(&scope).map_or(scope, str::to_string))
+}
+
+fn parse_namespace(namespace: &str) -> Result<String, String> {
+    let namespace = namespace.trim_matches('/');
+    if namespace.is_empty() {
+        return Err("the namespace cannot be empty".to_string());
+    }
+
+    Ok(namespace.to_string())
+}
+
+fn parse_tag_ref_format(format: &str) -> Result<String, String> {
+    if !format.starts_with("refs/tags/") || format.matches("{name}").count() != 1 {
+        return Err("the format must start with refs/tags/ and contain {name} once".to_string());
+    }
+
+    Ok(format.to_string())
+}
+
+fn parse_pattern(pattern: &str) -> Result<Pattern, String> {
+    Pattern::new(pattern).map_err(|e| e.to_string())
+}
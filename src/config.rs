@@ -0,0 +1,150 @@
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Value};
+
+pub const CONFIG_FILE: &str = ".semtag.toml";
+
+/// Repository defaults read from `.semtag.toml`, the command line takes precedence
+#[derive(Debug, Default)]
+pub struct Config {
+    /// Used when neither `--prefix` nor `--pattern` is given
+    pub prefix: Option<String>,
+    /// Takes precedence over the `semtag.annotate` git config
+    pub annotate: Option<bool>,
+}
+
+/// Where a setting shown by `--config-show` came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Flag,
+    Env,
+    ConfigFile,
+    GitConfig,
+    Default,
+}
+
+impl Source {
+    /// The source of the argument's value, `Default` when it was neither passed nor set in the
+    /// environment
+    pub fn of(matches: &ArgMatches, id: &str) -> Self {
+        match matches.value_source(id) {
+            Some(ValueSource::CommandLine) => Self::Flag,
+            Some(ValueSource::EnvVariable) => Self::Env,
+            _ => Self::Default,
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self {
+            Self::Flag => "flag",
+            Self::Env => "environment",
+            Self::ConfigFile => CONFIG_FILE,
+            Self::GitConfig => "git config",
+            Self::Default => "default",
+        };
+        f.write_str(source)
+    }
+}
+
+/// Renders the resolved settings in the format of the config file, each followed by its source
+/// as a comment
+pub fn render_resolved(settings: &[(&str, Value, Source)]) -> String {
+    let mut document = DocumentMut::new();
+    for (key, value, source) in settings {
+        let mut value = value.clone();
+        value.decor_mut().set_suffix(format!(" # {}", source));
+        document[*key] = Item::Value(value);
+    }
+    document.to_string()
+}
+
+/// The config file in the given directory, usually the root of the working tree
+pub fn config_path(dir: &Path) -> PathBuf {
+    dir.join(CONFIG_FILE)
+}
+
+fn string_value(item: &Item, key: &str, path: &Path) -> Result<String, String> {
+    item.as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("'{}': '{}' must be a string", path.display(), key))
+}
+
+fn bool_value(item: &Item, key: &str, path: &Path) -> Result<bool, String> {
+    item.as_bool()
+        .ok_or_else(|| format!("'{}': '{}' must be a boolean", path.display(), key))
+}
+
+/// Reads the config file, a missing file yields the defaults. Files checked out with Windows line
+/// endings are read as if they had Unix ones, so multi-line values never carry a `\r`.
+pub fn load(path: &Path) -> Result<Config, String> {
+    if !path.is_file() {
+        return Ok(Config::default());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+    let document = content
+        .replace("\r\n", "\n")
+        .parse::<DocumentMut>()
+        .map_err(|e| format!("Failed to parse '{}': {}", path.display(), e))?;
+
+    let mut config = Config::default();
+    for (key, item) in document.iter() {
+        match key {
+            "prefix" => config.prefix = Some(string_value(item, key, path)?),
+            "annotate" => config.annotate = Some(bool_value(item, key, path)?),
+            _ => return Err(format!("'{}': unknown key '{}'", path.display(), key)),
+        }
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovers_and_parses_a_config_with_windows_line_endings() {
+        let dir = tempfile::tempdir().unwrap();
+        let root: PathBuf = dir.path().join("checkout");
+        fs::create_dir(&root).unwrap();
+        fs::write(
+            root.join(CONFIG_FILE),
+            "# release settings\r\nprefix = \"v\"\r\nannotate = false\r\n",
+        )
+        .unwrap();
+
+        let path = config_path(&root);
+        assert_eq!(path, root.join(".semtag.toml"));
+        let config = load(&path).unwrap();
+        assert_eq!(config.prefix.as_deref(), Some("v"));
+        assert_eq!(config.annotate, Some(false));
+    }
+
+    #[test]
+    fn missing_file_yields_the_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = load(&config_path(dir.path())).unwrap();
+        assert_eq!(config.prefix, None);
+        assert_eq!(config.annotate, None);
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_wrong_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = config_path(dir.path());
+
+        fs::write(&path, "prefixes = \"v\"\r\n").unwrap();
+        assert!(load(&path).unwrap_err().contains("unknown key 'prefixes'"));
+
+        fs::write(&path, "annotate = \"yes\"\n").unwrap();
+        assert!(load(&path)
+            .unwrap_err()
+            .contains("'annotate' must be a boolean"));
+    }
+}
@@ -0,0 +1,39 @@
+/// The values a `list --format` string can refer to
+pub struct TagFields<'a> {
+    pub name: &'a str,
+    pub version: String,
+    pub prefix: &'a str,
+    pub commit: String,
+    pub date: String,
+    pub tagger: &'a str,
+}
+
+/// Expands `%n` (tag name), `%v` (version without prefix), `%p` (prefix), `%c` (commit SHA),
+/// `%d` (tag date), `%a` (tagger), and `%%`, like `git log --format`. Any other `%` sequence is
+/// kept as is.
+pub fn render_tag_format(format: &str, fields: &TagFields) -> String {
+    let mut output = String::with_capacity(format.len());
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => output.push_str(fields.name),
+            Some('v') => output.push_str(&fields.version),
+            Some('p') => output.push_str(fields.prefix),
+            Some('c') => output.push_str(&fields.commit),
+            Some('d') => output.push_str(&fields.date),
+            Some('a') => output.push_str(fields.tagger),
+            Some('%') => output.push('%'),
+            Some(other) => {
+                output.push('%');
+                output.push(other);
+            }
+            None => output.push('%'),
+        }
+    }
+
+    output
+}
@@ -0,0 +1,1077 @@
+use git2::{
+    AutotagOption, BranchType, Commit, Cred, CredentialType, DiffStatsFormat, Direction, Error,
+    ErrorClass, ErrorCode, FetchOptions, ObjectType, Oid, PushOptions, Reference, RemoteCallbacks,
+    Repository, Revwalk, Signature,
+};
+use glob::Pattern;
+use semtag::Version;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs, thread};
+
+use crate::cli::TagSort;
+use crate::logging::{log_info, log_warn};
+use crate::{cache, debug, gpg};
+
+pub const DEFAULT_TAG_REF_FORMAT: &str = "refs/tags/{name}";
+
+#[derive(Debug, Default, Clone)]
+pub struct TagFilter {
+    /// Where the tags live when not directly under `refs/tags/`, e.g. `refs/tags/releases/{name}`
+    pub ref_format: Option<String>,
+    pub prefix: Option<String>,
+    /// A glob handed to libgit2 to only load the matching tag names, e.g. `release-*`
+    pub pattern: Option<String>,
+    pub annotated_only: bool,
+    pub include: Vec<Pattern>,
+    pub exclude: Vec<Pattern>,
+    /// Only consider the most recently created matching tags
+    pub max_tags: Option<usize>,
+    /// Orders pre-releases of the same version by these stages, see [`Version::cmp_by_stages`]
+    pub stages: Vec<String>,
+    /// Orders the tags by version or by creation time, the last tag is the latest either way
+    pub sort: TagSort,
+    /// Reads the tag names from this cache while it is newer than the refs
+    pub cache_file: Option<PathBuf>,
+}
+
+impl TagFilter {
+    /// The tag name around `{name}`, e.g. `("releases/", "")` for `refs/tags/releases/{name}`
+    fn ref_format_parts(&self) -> Option<(&str, &str)> {
+        self.ref_format
+            .as_deref()?
+            .strip_prefix("refs/tags/")?
+            .split_once("{name}")
+    }
+
+    /// Strips the ref format from the tag name, tags outside of it yield `None`
+    pub fn version_name<'a>(&self, tag: &'a str) -> Option<&'a str> {
+        let name = match self.ref_format_parts() {
+            Some((head, tail)) => tag.strip_prefix(head)?.strip_suffix(tail)?,
+            None => tag,
+        };
+
+        (!name.contains('/')).then_some(name)
+    }
+
+    /// Places the version into the ref format, e.g. releases/1.2.0
+    pub fn tag_name(&self, version: &str) -> String {
+        match self.ref_format_parts() {
+            Some((head, tail)) => format!("{}{}{}", head, version, tail),
+            None => version.to_string(),
+        }
+    }
+
+    /// The glob used to load the tag names, `--pattern` or one derived from the prefix and ref
+    /// format
+    fn glob(&self) -> Option<String> {
+        if let Some(pattern) = &self.pattern {
+            return Some(self.tag_name(pattern));
+        }
+
+        match &self.prefix {
+            Some(prefix) => Some(self.tag_name(&format!("{}-*", prefix))),
+            None if self.ref_format.is_some() => Some(self.tag_name("*")),
+            None => None,
+        }
+    }
+
+    /// A tag must match the ref format, the prefix and any include pattern, exclude patterns take
+    /// precedence
+    fn matches(&self, tag: &str, verbose: u8) -> bool {
+        let Some(name) = self.version_name(tag) else {
+            return false;
+        };
+        let matches_prefix = if let Some(prefix) = &self.prefix {
+            // The whole prefix must match, so that `api` does not pick up `api-gateway-1.0.0`
+            Version::parse(name)
+                .is_ok_and(|version| version.prefix.as_deref() == Some(prefix.as_str()))
+        } else if self.pattern.is_some() {
+            true
+        } else {
+            // Without a prefix only complete, unprefixed versions are candidates
+            Version::parse(name).is_ok_and(|version| version.prefix.is_none())
+        };
+        if !matches_prefix {
+            return false;
+        }
+
+        if !self.include.is_empty() && !self.include.iter().any(|pattern| pattern.matches(tag)) {
+            debug(
+                verbose,
+                2,
+                &format!("Skipping tag '{}' not matching any include pattern", tag),
+            );
+            return false;
+        }
+
+        if let Some(pattern) = self.exclude.iter().find(|pattern| pattern.matches(tag)) {
+            debug(
+                verbose,
+                2,
+                &format!("Excluding tag '{}' matching '{}'", tag, pattern),
+            );
+            return false;
+        }
+
+        true
+    }
+}
+
+/// Reads `semtag.annotate` from the git config, tags are lightweight unless it is true
+pub fn annotate_by_default(repo: &Repository) -> bool {
+    repo.config()
+        .and_then(|config| config.get_bool("semtag.annotate"))
+        .unwrap_or(false)
+}
+
+/// Looks the tag up by its full ref name. libgit2 reads `packed-refs` as well as the loose ref
+/// files, so tags packed by `git gc` or `git pack-refs` resolve like loose ones
+fn find_tag_reference<'repo>(
+    repo: &'repo Repository,
+    tag: &str,
+) -> Result<Reference<'repo>, Error> {
+    repo.find_reference(&format!("refs/tags/{}", tag))?
+        .resolve()
+}
+
+pub fn tag_exists(repo: &Repository, tag: &str) -> Result<bool, Error> {
+    match find_tag_reference(repo, tag) {
+        Ok(_) => Ok(true),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn is_annotated_tag(repo: &Repository, tag: &str) -> Result<bool, Error> {
+    let reference = find_tag_reference(repo, tag)?;
+    let target = reference
+        .target()
+        .ok_or_else(|| Error::from_str("Cannot resolve tag target"))?;
+    let object = repo.find_object(target, None)?;
+
+    Ok(object.kind() == Some(ObjectType::Tag))
+}
+
+/// The creation time of the tag, the tagger time for annotated tags and the commit time otherwise
+fn tag_timestamp(repo: &Repository, tag: &str) -> Result<i64, Error> {
+    let reference = find_tag_reference(repo, tag)?;
+    let tagger_time = reference
+        .peel_to_tag()
+        .ok()
+        .and_then(|tag| tag.tagger().map(|tagger| tagger.when().seconds()));
+    if let Some(tagger_time) = tagger_time {
+        return Ok(tagger_time);
+    }
+
+    Ok(reference.peel_to_commit()?.time().seconds())
+}
+
+/// Where a tag points and, for annotated tags, who created it and when
+pub struct TagDetails {
+    pub commit: Oid,
+    pub annotated: bool,
+    /// The tagger of annotated tags as `Name <email>`, lightweight tags have none
+    pub tagger: Option<String>,
+    /// The tagger time of annotated tags, lightweight tags have none
+    pub tagger_time: Option<i64>,
+    /// The tagger time, or the commit time for lightweight tags
+    pub created: i64,
+}
+
+pub fn tag_details(repo: &Repository, tag: &str) -> Result<TagDetails, Error> {
+    let reference = find_tag_reference(repo, tag)?;
+    let annotated = reference.peel_to_tag().ok();
+    let tagger = annotated.as_ref().and_then(|tag| tag.tagger());
+    let commit = reference.peel_to_commit()?;
+    let tagger_time = tagger.as_ref().map(|tagger| tagger.when().seconds());
+
+    Ok(TagDetails {
+        commit: commit.id(),
+        annotated: annotated.is_some(),
+        tagger: tagger.as_ref().map(|tagger| tagger.to_string()),
+        tagger_time,
+        created: tagger_time.unwrap_or_else(|| commit.time().seconds()),
+    })
+}
+
+/// The `max_tags` most recently created tags, newest first. Every tag has to be peeled for its
+/// timestamp, but only `max_tags` of them are kept at a time
+fn most_recent_tags(
+    repo: &Repository,
+    tags: Vec<String>,
+    max_tags: usize,
+    verbose: u8,
+) -> Result<Vec<String>, Error> {
+    if tags.len() <= max_tags {
+        return Ok(tags);
+    }
+
+    debug(
+        verbose,
+        1,
+        &format!(
+            "Keeping the {} most recent of {} tags",
+            max_tags,
+            tags.len()
+        ),
+    );
+    // A min-heap, so the oldest of the kept tags is the one dropped
+    let mut newest = BinaryHeap::with_capacity(max_tags + 1);
+    for tag in tags {
+        newest.push(Reverse((tag_timestamp(repo, &tag)?, tag)));
+        if newest.len() > max_tags {
+            newest.pop();
+        }
+    }
+
+    Ok(newest
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((_, tag))| tag)
+        .collect())
+}
+
+pub fn get_version_tags(
+    repo: &Repository,
+    filter: &TagFilter,
+    verbose: u8,
+) -> Result<Vec<String>, Error> {
+    let glob = filter.glob();
+    debug(verbose, 2, &format!("Tag glob: {:?}", glob));
+    let all_tags: Vec<String> = match (&filter.cache_file, &filter.ref_format, &glob) {
+        (Some(path), _, _) => {
+            let tags = match cache::load(repo, path) {
+                Some(tags) => {
+                    debug(
+                        verbose,
+                        1,
+                        &format!("Using the tag cache '{}'", path.display()),
+                    );
+                    tags
+                }
+                None => {
+                    let tags = cache::scan(repo)?;
+                    match cache::store(path, &tags) {
+                        Ok(()) => debug(
+                            verbose,
+                            1,
+                            &format!("Wrote the tag cache '{}'", path.display()),
+                        ),
+                        Err(e) => log_warn!("Warning: {}", e),
+                    }
+                    tags
+                }
+            };
+            let glob = glob.as_deref().and_then(|glob| Pattern::new(glob).ok());
+            tags.into_iter()
+                .filter(|name| glob.as_ref().is_none_or(|glob| glob.matches(name)))
+                .collect()
+        }
+        (None, Some(_), Some(glob)) => {
+            let mut references = repo.references_glob(&format!("refs/tags/{}", glob))?;
+            references
+                .names()
+                .flatten()
+                .filter_map(|name| name.strip_prefix("refs/tags/"))
+                .map(|tag| tag.to_string())
+                .collect()
+        }
+        _ => {
+            let tags = repo.tag_names(glob.as_deref())?;
+            tags.iter().flatten().map(|tag| tag.to_string()).collect()
+        }
+    };
+    debug(verbose, 1, &format!("All tags: {:?}", all_tags));
+
+    let filtered_tags: Vec<String> = all_tags
+        .into_iter()
+        .filter(|tag| filter.matches(tag, verbose))
+        .collect();
+
+    let filtered_tags = if filter.annotated_only {
+        let mut annotated_tags = Vec::new();
+        for tag in filtered_tags {
+            if is_annotated_tag(repo, &tag)? {
+                annotated_tags.push(tag);
+            } else {
+                debug(verbose, 2, &format!("Skipping lightweight tag '{}'", tag));
+            }
+        }
+        annotated_tags
+    } else {
+        filtered_tags
+    };
+    let filtered_tags = match filter.max_tags {
+        Some(max_tags) => most_recent_tags(repo, filtered_tags, max_tags, verbose)?,
+        None => filtered_tags,
+    };
+    debug(verbose, 1, &format!("Filtered tags: {:?}", filtered_tags));
+
+    let mut versions: Vec<(Version, String)> = filtered_tags
+        .into_iter()
+        .filter_map(
+            |tag| match Version::parse(filter.version_name(&tag).unwrap_or(&tag)) {
+                Ok(version) => Some((version, tag)),
+                Err(e) => {
+                    debug(
+                        verbose,
+                        2,
+                        &format!("Skipping unparsable tag '{}': {}", tag, e),
+                    );
+                    None
+                }
+            },
+        )
+        .collect();
+    let stages: Vec<&str> = filter.stages.iter().map(String::as_str).collect();
+    versions.sort_by(|(a, _), (b, _)| a.cmp_by_stages(b, &stages));
+
+    if filter.sort == TagSort::Created {
+        let mut created = Vec::with_capacity(versions.len());
+        for (_, tag) in versions {
+            created.push((tag_timestamp(repo, &tag)?, tag));
+        }
+        // The sort is stable, so tags created in the same second stay in version order
+        created.sort_by_key(|(timestamp, _)| *timestamp);
+        return Ok(created.into_iter().map(|(_, tag)| tag).collect());
+    }
+
+    Ok(versions.into_iter().map(|(_, tag)| tag).collect())
+}
+
+pub fn get_latest_git_tag(
+    repo: &Repository,
+    filter: &TagFilter,
+    option: Option<&str>,
+    verbose: u8,
+) -> Result<String, Error> {
+    let filtered_tags = get_version_tags(repo, filter, verbose)?;
+
+    if filtered_tags.is_empty() {
+        let initial = Version::initial(filter.prefix.as_deref(), option);
+        let tag = filter.tag_name(&initial.to_string());
+        debug(
+            verbose,
+            1,
+            &format!("No matching tags, starting from '{}'", tag),
+        );
+        Ok(tag)
+    } else {
+        let tag = filtered_tags.last().unwrap().to_string();
+        debug(verbose, 1, &format!("Latest tag: '{}'", tag));
+        Ok(tag.to_string())
+    }
+}
+
+/// Checks whether HEAD points to a commit, it is unborn in a repository without commits
+pub fn has_commits(repo: &Repository) -> Result<bool, Error> {
+    match repo.head() {
+        Ok(_) => Ok(true),
+        Err(e) if e.code() == ErrorCode::UnbornBranch || e.code() == ErrorCode::NotFound => {
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+pub fn resolve_head_commit(repo: &Repository) -> Result<Commit<'_>, Error> {
+    if !has_commits(repo)? {
+        return Err(Error::from_str(
+            "No commits to tag, the current branch has no commits yet",
+        ));
+    }
+    let commit = repo.head()?.peel_to_commit()?;
+
+    if repo.head_detached()? {
+        log_info!("Note: HEAD is detached, tagging commit {}", commit.id());
+    }
+
+    Ok(commit)
+}
+
+/// Checks whether the tag already points at the HEAD commit, i.e. nothing happened since
+pub fn tag_points_at_head(repo: &Repository, tag: &str) -> Result<bool, Error> {
+    if !has_commits(repo)? {
+        return Ok(false);
+    }
+    let tagged = match find_tag_reference(repo, tag) {
+        Ok(reference) => reference.peel_to_commit()?.id(),
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+
+    Ok(repo.head()?.peel_to_commit()?.id() == tagged)
+}
+
+/// Checks whether the HEAD commit message starts with the pattern, e.g. `chore(release):`
+pub fn is_release_commit(repo: &Repository, pattern: &str) -> Result<bool, Error> {
+    if !has_commits(repo)? {
+        return Ok(false);
+    }
+    let commit = repo.head()?.peel_to_commit()?;
+    let message = commit.message().unwrap_or_default();
+
+    Ok(message.trim_start().starts_with(pattern))
+}
+
+/// A commit in the changelog of a release
+#[derive(Debug)]
+pub struct CommitSummary {
+    pub short_id: String,
+    pub summary: String,
+}
+
+/// Walks the commits reachable from HEAD but not from the tag, all of them if the tag does not
+/// exist. With `first_parent` only the first parent of merge commits is followed, like
+/// `git log --first-parent`. The walk is empty while HEAD is unborn.
+fn revwalk_since<'repo>(
+    repo: &'repo Repository,
+    tag: &str,
+    first_parent: bool,
+) -> Result<Revwalk<'repo>, Error> {
+    let mut revwalk = repo.revwalk()?;
+    if !has_commits(repo)? {
+        return Ok(revwalk);
+    }
+    revwalk.push_head()?;
+    if first_parent {
+        revwalk.simplify_first_parent()?;
+    }
+
+    match find_tag_reference(repo, tag) {
+        Ok(reference) => revwalk.hide(reference.peel_to_commit()?.id())?,
+        Err(e) if e.code() == ErrorCode::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    Ok(revwalk)
+}
+
+/// Counts the commits since the tag, see [`revwalk_since`]
+pub fn count_commits_since(
+    repo: &Repository,
+    tag: &str,
+    first_parent: bool,
+) -> Result<usize, Error> {
+    Ok(revwalk_since(repo, tag, first_parent)?.count())
+}
+
+/// Lists the commits since the tag, newest first, see [`revwalk_since`]
+pub fn commits_since(
+    repo: &Repository,
+    tag: &str,
+    first_parent: bool,
+) -> Result<Vec<CommitSummary>, Error> {
+    let mut commits = Vec::new();
+    for oid in revwalk_since(repo, tag, first_parent)? {
+        let commit = repo.find_commit(oid?)?;
+        let id = commit.id().to_string();
+        commits.push(CommitSummary {
+            short_id: id[..7].to_string(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+        });
+    }
+
+    Ok(commits)
+}
+
+/// The full messages of the commits since the tag, see [`commits_since`]
+pub fn commit_messages_since(
+    repo: &Repository,
+    tag: &str,
+    first_parent: bool,
+) -> Result<Vec<String>, Error> {
+    let mut messages = Vec::new();
+    for oid in revwalk_since(repo, tag, first_parent)? {
+        let commit = repo.find_commit(oid?)?;
+        messages.push(commit.message().unwrap_or_default().to_string());
+    }
+
+    Ok(messages)
+}
+
+/// Summarizes the changes between the commits of two tags like `git diff --shortstat`, e.g.
+/// "3 files changed, 10 insertions(+), 2 deletions(-)"
+pub fn diff_stats(repo: &Repository, from: &str, to: &str) -> Result<String, Error> {
+    let old_tree = find_tag_reference(repo, from)?.peel_to_tree()?;
+    let new_tree = find_tag_reference(repo, to)?.peel_to_tree()?;
+    let diff = repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?;
+    let stats = diff.stats()?;
+    if stats.files_changed() == 0 {
+        return Ok("no changes".to_string());
+    }
+    let summary = stats.to_buf(DiffStatsFormat::SHORT, 80)?;
+
+    Ok(summary.as_str().unwrap_or_default().trim().to_string())
+}
+
+/// Checks whether any file under `path`, relative to the repository root, differs between the
+/// tag and HEAD. Everything counts as changed when the tag does not exist yet, nothing while HEAD
+/// is unborn.
+pub fn changed_since(repo: &Repository, tag: &str, path: &Path) -> Result<bool, Error> {
+    if !has_commits(repo)? {
+        return Ok(false);
+    }
+    let old_tree = match find_tag_reference(repo, tag) {
+        Ok(reference) => reference.peel_to_tree()?,
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(true),
+        Err(e) => return Err(e),
+    };
+    let new_tree = repo.head()?.peel_to_tree()?;
+
+    let diff = repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?;
+    let changed = diff.deltas().any(|delta| {
+        [delta.old_file().path(), delta.new_file().path()]
+            .into_iter()
+            .flatten()
+            .any(|file| file.starts_with(path))
+    });
+
+    Ok(changed)
+}
+
+/// Checks the tag against git's ref-name rules, e.g. no `..`, spaces, or control characters
+pub fn is_valid_tag_name(tag: &str) -> bool {
+    Reference::is_valid_name(&format!("refs/tags/{}", tag))
+}
+
+#[derive(Debug, Default)]
+pub struct TagOptions {
+    /// Creates an annotated tag with this message, a lightweight tag otherwise
+    pub message: Option<String>,
+    /// Signs the annotated tag with gpg
+    pub sign: bool,
+    /// The gpg key to sign with, the default key is used otherwise
+    pub sign_key: Option<String>,
+    pub gpg_program: Option<PathBuf>,
+}
+
+fn shell_quote(value: &str) -> String {
+    let is_safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "._/+-".contains(c));
+
+    if is_safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Renders the `git tag` command equivalent to creating the tag with the given options
+pub fn git_tag_command(tag: &str, options: &TagOptions, target: &str) -> String {
+    let mut command = vec!["git".to_string(), "tag".to_string()];
+
+    if let Some(message) = &options.message {
+        match (options.sign, &options.sign_key) {
+            (true, Some(key)) => {
+                command.push("-u".to_string());
+                command.push(shell_quote(key));
+            }
+            (true, None) => command.push("-s".to_string()),
+            (false, _) => command.push("-a".to_string()),
+        }
+        command.push("-m".to_string());
+        command.push(shell_quote(message));
+    }
+    command.push(shell_quote(tag));
+    command.push(target.to_string());
+
+    command.join(" ")
+}
+
+fn env_identity(keys: [&str; 2]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| env::var(key).ok())
+        .find(|value| !value.trim().is_empty())
+}
+
+/// Resolves the tagger from the git config, falling back to the standard git environment variables
+pub fn resolve_signature(repo: &Repository) -> Result<Signature<'static>, Error> {
+    if let Ok(signature) = repo.signature() {
+        return Ok(signature);
+    }
+
+    let name = env_identity(["GIT_COMMITTER_NAME", "GIT_AUTHOR_NAME"]);
+    let email = env_identity(["GIT_COMMITTER_EMAIL", "GIT_AUTHOR_EMAIL"]);
+
+    match (name, email) {
+        (Some(name), Some(email)) => Signature::now(&name, &email),
+        _ => Err(Error::from_str(
+            "No identity found, set user.name and user.email in the git config or the GIT_COMMITTER_NAME and GIT_COMMITTER_EMAIL environment variables",
+        )),
+    }
+}
+
+fn format_signature(signature: &Signature) -> String {
+    let when = signature.when();
+    let offset = when.offset_minutes();
+    let sign = if offset < 0 { '-' } else { '+' };
+
+    format!(
+        "{} <{}> {} {}{:02}{:02}",
+        signature.name().unwrap_or_default(),
+        signature.email().unwrap_or_default(),
+        when.seconds(),
+        sign,
+        offset.abs() / 60,
+        offset.abs() % 60
+    )
+}
+
+fn create_signed_tag(
+    repo: &Repository,
+    tag: &str,
+    commit: &Commit,
+    message: &str,
+    options: &TagOptions,
+) -> Result<(), Error> {
+    let tagger = resolve_signature(repo)?;
+    let mut message = message.to_string();
+    if !message.ends_with('\n') {
+        message.push('\n');
+    }
+
+    let payload = format!(
+        "object {}\ntype commit\ntag {}\ntagger {}\n\n{}",
+        commit.id(),
+        tag,
+        format_signature(&tagger),
+        message
+    );
+    let signature = gpg::sign(
+        &payload,
+        options.sign_key.as_deref(),
+        options.gpg_program.as_deref(),
+    )?;
+
+    let oid = repo.odb()?.write(
+        ObjectType::Tag,
+        format!("{}{}", payload, signature).as_bytes(),
+    )?;
+    repo.reference(
+        &format!("refs/tags/{}", tag),
+        oid,
+        false,
+        &format!("semtag: signed tag {}", tag),
+    )?;
+
+    Ok(())
+}
+
+/// Creates the tag on HEAD and returns the tagged commit
+pub fn create_git_tag(repo: &Repository, tag: &str, options: &TagOptions) -> Result<Oid, Error> {
+    let commit = resolve_head_commit(repo)?;
+    let object = commit.as_object();
+
+    match options.message.as_deref() {
+        Some(message) if options.sign => {
+            create_signed_tag(repo, tag, &commit, message, options)?;
+        }
+        Some(message) => {
+            let signature = resolve_signature(repo)?;
+            repo.tag(tag, object, &signature, message, false)?;
+        }
+        None => {
+            repo.tag_lightweight(tag, object, false)?;
+        }
+    }
+
+    Ok(commit.id())
+}
+
+pub fn branch_exists(repo: &Repository, name: &str) -> Result<bool, Error> {
+    match repo.find_branch(name, BranchType::Local) {
+        Ok(_) => Ok(true),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Creates a local branch on the tagged commit, `force` overwrites an existing branch
+pub fn create_branch(repo: &Repository, name: &str, target: Oid, force: bool) -> Result<(), Error> {
+    let commit = repo.find_commit(target)?;
+    repo.branch(name, &commit, force)?;
+
+    Ok(())
+}
+
+/// Adds the file to the index, like `git add <path>`
+pub fn stage_file(repo: &Repository, path: &Path) -> Result<(), Error> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| Error::from_str("Cannot stage files in a bare repository"))?;
+    let workdir = fs::canonicalize(workdir).map_err(|e| Error::from_str(&e.to_string()))?;
+    let path = fs::canonicalize(path).map_err(|e| Error::from_str(&e.to_string()))?;
+    let relative_path = path.strip_prefix(&workdir).map_err(|_| {
+        Error::from_str(&format!(
+            "'{}' is outside of the repository",
+            path.display()
+        ))
+    })?;
+
+    let mut index = repo.index()?;
+    index.add_path(relative_path)?;
+    index.write()
+}
+
+pub fn delete_git_tag(repo: &Repository, tag: &str) -> Result<(), Error> {
+    repo.tag_delete(tag)?;
+    println!("Tag '{}' deleted successfully", tag);
+
+    Ok(())
+}
+
+/// Authenticates with the ssh agent or the git credential helpers. libgit2 asks again after a
+/// rejected credential, so the second request fails instead of looping forever.
+fn remote_callbacks(repo: &Repository) -> RemoteCallbacks<'_> {
+    let config = repo.config();
+    let mut attempted = false;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        if attempted {
+            return Err(Error::new(
+                ErrorCode::Auth,
+                ErrorClass::Net,
+                "Authentication failed, check the ssh agent or the git credential helper",
+            ));
+        }
+        attempted = true;
+        if allowed.contains(CredentialType::SSH_KEY) {
+            Cred::ssh_key_from_agent(username.unwrap_or("git"))
+        } else if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            let config = config.as_ref().map_err(|e| Error::from_str(e.message()))?;
+            Cred::credential_helper(config, url, username)
+        } else {
+            Cred::default()
+        }
+    });
+    callbacks
+}
+
+/// Network hiccups are worth another attempt, rejected credentials and certificates are not
+fn is_transient(e: &Error) -> bool {
+    !matches!(e.code(), ErrorCode::Auth | ErrorCode::Certificate)
+        && matches!(
+            e.class(),
+            ErrorClass::Net | ErrorClass::Os | ErrorClass::Http | ErrorClass::Ssh
+        )
+}
+
+/// The wait before the given retry, 1s, 2s, 4s, ... capped at 64s
+fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1 << attempt.min(6))
+}
+
+/// Runs a network operation up to `retries` more times after a transient failure, waiting 1s,
+/// 2s, 4s, ... in between. Only the last error is returned.
+fn with_retries(
+    retries: u32,
+    verbose: u8,
+    action: &str,
+    operation: impl FnMut() -> Result<(), Error>,
+) -> Result<(), Error> {
+    retry(retries, verbose, action, operation, thread::sleep)
+}
+
+/// [`with_retries`] with the wait between the attempts left to `sleep`
+fn retry(
+    retries: u32,
+    verbose: u8,
+    action: &str,
+    mut operation: impl FnMut() -> Result<(), Error>,
+    mut sleep: impl FnMut(Duration),
+) -> Result<(), Error> {
+    let mut attempt = 0;
+    loop {
+        match operation() {
+            Err(e) if attempt < retries && is_transient(&e) => {
+                let delay = retry_delay(attempt);
+                attempt += 1;
+                debug(
+                    verbose,
+                    1,
+                    &format!(
+                        "{} failed ({}), retrying in {}s ({}/{})",
+                        action,
+                        e.message(),
+                        delay.as_secs(),
+                        attempt,
+                        retries
+                    ),
+                );
+                sleep(delay);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Fetches all tags from the remote, like `git fetch --tags`
+pub fn fetch_tags(repo: &Repository, remote: &str, retries: u32, verbose: u8) -> Result<(), Error> {
+    let mut remote = repo.find_remote(remote)?;
+    with_retries(retries, verbose, "Fetch", || {
+        let mut options = FetchOptions::new();
+        options
+            .remote_callbacks(remote_callbacks(repo))
+            .download_tags(AutotagOption::All);
+        remote.fetch::<&str>(&[], Some(&mut options), None)
+    })
+}
+
+/// Lists the tags of the remote passing the filter that do not exist locally, like
+/// `git ls-remote --tags` without fetching anything
+pub fn remote_only_tags(
+    repo: &Repository,
+    filter: &TagFilter,
+    remote: &str,
+    retries: u32,
+    verbose: u8,
+) -> Result<Vec<String>, Error> {
+    let mut remote = repo.find_remote(remote)?;
+    let mut remote_tags = Vec::new();
+    with_retries(retries, verbose, "Listing the remote", || {
+        let connection =
+            remote.connect_auth(Direction::Fetch, Some(remote_callbacks(repo)), None)?;
+        remote_tags = connection
+            .list()?
+            .iter()
+            .filter_map(|head| head.name().strip_prefix("refs/tags/"))
+            // Peeled annotated tags are advertised a second time as `<tag>^{}`
+            .filter(|tag| !tag.ends_with("^{}"))
+            .map(|tag| tag.to_string())
+            .collect();
+        Ok(())
+    })?;
+    debug(verbose, 1, &format!("Remote tags: {:?}", remote_tags));
+
+    let glob = filter.glob().and_then(|glob| Pattern::new(&glob).ok());
+    Ok(remote_tags
+        .into_iter()
+        .filter(|tag| glob.as_ref().is_none_or(|glob| glob.matches(tag)))
+        .filter(|tag| filter.matches(tag, verbose))
+        .filter(|tag| repo.find_reference(&format!("refs/tags/{}", tag)).is_err())
+        .collect())
+}
+
+/// Pushes the tag to the remote, a rejected push is an error rather than a silent no-op
+pub fn push_tag(
+    repo: &Repository,
+    remote: &str,
+    tag: &str,
+    retries: u32,
+    verbose: u8,
+) -> Result<(), Error> {
+    let mut remote = repo.find_remote(remote)?;
+    let refspec = format!("refs/tags/{0}:refs/tags/{0}", tag);
+    with_retries(retries, verbose, "Push", || {
+        let mut rejection = None;
+        let mut callbacks = remote_callbacks(repo);
+        callbacks.push_update_reference(|reference, status| {
+            if let Some(status) = status {
+                rejection = Some(format!("'{}' was rejected: {}", reference, status));
+            }
+            Ok(())
+        });
+        let mut options = PushOptions::new();
+        options.remote_callbacks(callbacks);
+        remote.push(&[refspec.as_str()], Some(&mut options))?;
+        drop(options);
+
+        match rejection {
+            Some(rejection) => Err(Error::from_str(&rejection)),
+            None => Ok(()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_does_not_match_a_longer_prefix() {
+        let filter = TagFilter {
+            prefix: Some("api".to_string()),
+            ..TagFilter::default()
+        };
+        assert!(filter.matches("api-1.0.0", 0));
+        assert!(filter.matches("api-1.1.0-rc.1", 0));
+        assert!(!filter.matches("api-gateway-2.0.0", 0));
+
+        let filter = TagFilter {
+            prefix: Some("api-gateway".to_string()),
+            ..TagFilter::default()
+        };
+        assert!(filter.matches("api-gateway-2.0.0", 0));
+        assert!(!filter.matches("api-1.0.0", 0));
+    }
+
+    #[test]
+    fn short_prefix_does_not_match_a_word() {
+        let filter = TagFilter {
+            prefix: Some("v".to_string()),
+            ..TagFilter::default()
+        };
+        assert!(filter.matches("v-1.2.3", 0));
+        assert!(!filter.matches("vendor-1.2.3", 0));
+    }
+
+    fn network_error(message: &str) -> Error {
+        Error::new(ErrorCode::GenericError, ErrorClass::Net, message)
+    }
+
+    /// Runs [`retry`] over the given results, returning the outcome, the number of attempts, and
+    /// the waits in seconds
+    fn run_retry(
+        retries: u32,
+        results: Vec<Result<(), Error>>,
+    ) -> (Result<(), Error>, usize, Vec<u64>) {
+        let mut results = results.into_iter();
+        let mut attempts = 0;
+        let mut delays = Vec::new();
+        let result = retry(
+            retries,
+            0,
+            "Fetch",
+            || {
+                attempts += 1;
+                results.next().unwrap_or(Ok(()))
+            },
+            |delay| delays.push(delay.as_secs()),
+        );
+
+        (result, attempts, delays)
+    }
+
+    #[test]
+    fn retry_backs_off_until_the_retries_run_out() {
+        let failures = (1..=4)
+            .map(|attempt| Err(network_error(&format!("attempt {}", attempt))))
+            .collect();
+        let (result, attempts, delays) = run_retry(3, failures);
+
+        assert_eq!(result.unwrap_err().message(), "attempt 4");
+        assert_eq!(attempts, 4);
+        assert_eq!(delays, [1, 2, 4]);
+    }
+
+    #[test]
+    fn retry_stops_at_the_first_success() {
+        let results = vec![Err(network_error("timeout")), Ok(())];
+        let (result, attempts, delays) = run_retry(3, results);
+
+        assert!(result.is_ok());
+        assert_eq!(attempts, 2);
+        assert_eq!(delays, [1]);
+    }
+
+    #[test]
+    fn retry_gives_up_on_permanent_errors() {
+        let auth = Error::new(ErrorCode::Auth, ErrorClass::Net, "denied");
+        let (result, attempts, delays) = run_retry(3, vec![Err(auth)]);
+        assert_eq!(result.unwrap_err().code(), ErrorCode::Auth);
+        assert_eq!((attempts, delays.len()), (1, 0));
+
+        let config = Error::new(ErrorCode::GenericError, ErrorClass::Config, "bad remote");
+        let (_, attempts, _) = run_retry(3, vec![Err(config)]);
+        assert_eq!(attempts, 1);
+
+        let (_, attempts, _) = run_retry(0, vec![Err(network_error("timeout"))]);
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn retry_delay_is_capped() {
+        let delays: Vec<u64> = (0..9)
+            .map(|attempt| retry_delay(attempt).as_secs())
+            .collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 32, 64, 64, 64]);
+    }
+
+    /// A repository with one commit per tag, committed at the given time
+    fn repo_with_tags(tags: &[(&str, i64)]) -> (tempfile::TempDir, Repository) {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        {
+            let tree_id = repo.index().unwrap().write_tree().unwrap();
+            let tree = repo.find_tree(tree_id).unwrap();
+            let mut parent: Option<Commit> = None;
+            for (tag, seconds) in tags {
+                let signature = Signature::new(
+                    "semtag",
+                    "semtag@example.com",
+                    &git2::Time::new(*seconds, 0),
+                )
+                .unwrap();
+                let parents: Vec<&Commit> = parent.iter().collect();
+                let id = repo
+                    .commit(Some("HEAD"), &signature, &signature, tag, &tree, &parents)
+                    .unwrap();
+                let commit = repo.find_commit(id).unwrap();
+                repo.tag_lightweight(tag, commit.as_object(), false)
+                    .unwrap();
+                parent = Some(commit);
+            }
+        }
+
+        (dir, repo)
+    }
+
+    #[test]
+    fn most_recent_tags_keeps_the_newest_first() {
+        let (_dir, repo) = repo_with_tags(&[
+            ("1.0.0", 1_000),
+            ("1.2.0", 4_000),
+            ("1.1.0", 2_000),
+            ("0.9.0", 3_000),
+        ]);
+        let tags = ["1.0.0", "1.2.0", "1.1.0", "0.9.0"]
+            .map(String::from)
+            .to_vec();
+
+        assert_eq!(
+            most_recent_tags(&repo, tags.clone(), 2, 0).unwrap(),
+            ["1.2.0", "0.9.0"]
+        );
+        assert_eq!(most_recent_tags(&repo, tags.clone(), 4, 0).unwrap(), tags);
+        assert!(most_recent_tags(&repo, tags, 0, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn packed_tags_resolve_like_loose_ones() {
+        let (dir, repo) = repo_with_tags(&[("1.0.0", 1_000)]);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let signature = head.author();
+        repo.tag(
+            "1.1.0",
+            head.as_object(),
+            &signature,
+            "Release 1.1.0",
+            false,
+        )
+        .unwrap();
+
+        let status = std::process::Command::new("git")
+            .args(["pack-refs", "--all"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert!(!dir.path().join(".git/refs/tags/1.1.0").exists());
+
+        assert!(tag_exists(&repo, "1.0.0").unwrap());
+        assert!(!tag_exists(&repo, "2.0.0").unwrap());
+        assert!(!is_annotated_tag(&repo, "1.0.0").unwrap());
+        assert!(is_annotated_tag(&repo, "1.1.0").unwrap());
+        assert_eq!(tag_details(&repo, "1.1.0").unwrap().commit, head.id());
+        assert_eq!(
+            get_version_tags(&repo, &TagFilter::default(), 0).unwrap(),
+            ["1.0.0", "1.1.0"]
+        );
+    }
+}
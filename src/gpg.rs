@@ -0,0 +1,66 @@
+use git2::Error;
+use std::ffi::OsStr;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const DEFAULT_GPG_PROGRAMS: [&str; 2] = ["gpg2", "gpg"];
+
+/// Creates an armored detached signature of the payload, trying gpg2 then gpg by default
+pub fn sign(payload: &str, key_id: Option<&str>, program: Option<&Path>) -> Result<String, Error> {
+    let programs: Vec<&OsStr> = match program {
+        Some(program) => vec![program.as_os_str()],
+        None => DEFAULT_GPG_PROGRAMS
+            .iter()
+            .map(|program| OsStr::new(*program))
+            .collect(),
+    };
+
+    for program in programs {
+        let mut command = Command::new(program);
+        command.args(["--detach-sign", "--armor"]);
+        if let Some(key_id) = key_id {
+            command.args(["--local-user", key_id]);
+        }
+
+        let mut child = match command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => {
+                return Err(Error::from_str(&format!(
+                    "Failed to run '{}': {}",
+                    program.to_string_lossy(),
+                    e
+                )));
+            }
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(payload.as_bytes())
+                .map_err(|e| Error::from_str(&format!("Failed to write to gpg: {}", e)))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| Error::from_str(&format!("Failed to wait for gpg: {}", e)))?;
+        if !output.status.success() {
+            return Err(Error::from_str(&format!(
+                "'{}' failed to sign the tag",
+                program.to_string_lossy()
+            )));
+        }
+
+        return String::from_utf8(output.stdout)
+            .map_err(|_| Error::from_str("gpg produced a signature that is not valid UTF-8"));
+    }
+
+    Err(Error::from_str(
+        "No gpg program found, install gpg or pass --gpg-program",
+    ))
+}
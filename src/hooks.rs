@@ -0,0 +1,23 @@
+use std::io;
+use std::process::{Command, ExitStatus};
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+/// Runs the hook through the platform shell with the given environment variables
+pub fn run_hook(command: &str, envs: &[(&str, &str)]) -> io::Result<ExitStatus> {
+    shell_command(command)
+        .envs(envs.iter().map(|(key, value)| (*key, *value)))
+        .status()
+}
@@ -0,0 +1,7 @@
+mod version;
+
+pub use version::{
+    as_string, detect_scope_from_commits, is_semver, normalize_scope, PrereleaseStage, SemtagError,
+    Version, VersionDistance, DEFAULT_OPTIONS, OPT_ALPHA, OPT_BETA, OPT_RC, SCOPE_ALIASES,
+    SCOPE_AUTO, SCOPE_MAJOR, SCOPE_MINOR, SCOPE_NONE, SCOPE_PATCH,
+};
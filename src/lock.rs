@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::Path;
+use toml_edit::{value, Datetime, DocumentMut};
+
+use crate::timestamp;
+
+pub const LOCK_FILE: &str = "semtag.lock";
+
+/// The last version transition recorded in `semtag.lock`
+#[derive(Debug)]
+pub struct LockEntry<'a> {
+    pub previous: &'a str,
+    pub current: &'a str,
+    pub commit: String,
+    pub scope: &'a str,
+}
+
+/// Writes the entry to the lock file, keeping any other keys and comments of an existing file
+pub fn write_lock_file(path: &Path, entry: &LockEntry) -> Result<(), String> {
+    let mut document = match fs::read_to_string(path) {
+        Ok(content) => content
+            .parse::<DocumentMut>()
+            .map_err(|e| format!("Failed to parse '{}': {}", path.display(), e))?,
+        Err(_) => DocumentMut::new(),
+    };
+
+    let timestamp = timestamp::format_rfc3339(timestamp::now())
+        .parse::<Datetime>()
+        .map_err(|e| e.to_string())?;
+
+    document["previous"] = value(entry.previous);
+    document["current"] = value(entry.current);
+    document["timestamp"] = value(timestamp);
+    document["commit"] = value(entry.commit.as_str());
+    document["scope"] = value(entry.scope);
+
+    fs::write(path, document.to_string())
+        .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+}
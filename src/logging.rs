@@ -0,0 +1,74 @@
+use std::env;
+use std::io;
+use std::sync::OnceLock;
+use tracing_subscriber::EnvFilter;
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// Installs a `tracing` subscriber writing to stderr when `SEMTAG_LOG` or `RUST_LOG` is set, e.g.
+/// `SEMTAG_LOG=debug`, so that embedding tools can collect semtag's diagnostics as events.
+/// Without either variable the diagnostics keep their plain format.
+pub fn init() {
+    let directives = ["SEMTAG_LOG", "RUST_LOG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()));
+    let enabled = directives.is_some_and(|directives| {
+        tracing_subscriber::fmt()
+            .with_env_filter(EnvFilter::new(directives))
+            .with_writer(io::stderr)
+            .try_init()
+            .is_ok()
+    });
+    let _ = ENABLED.set(enabled);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.get().copied().unwrap_or(false)
+}
+
+pub fn error(message: &str) {
+    if enabled() {
+        tracing::error!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+pub fn warn(message: &str) {
+    if enabled() {
+        tracing::warn!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+pub fn info(message: &str) {
+    if enabled() {
+        tracing::info!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+/// Reports an error on stderr, see [`init`]
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        $crate::logging::error(&format!($($arg)*))
+    };
+}
+
+/// Reports a warning on stderr, see [`init`]
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        $crate::logging::warn(&format!($($arg)*))
+    };
+}
+
+/// Reports a note on stderr, see [`init`]
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        $crate::logging::info(&format!($($arg)*))
+    };
+}
+
+pub(crate) use {log_error, log_info, log_warn};
@@ -1,287 +1,1541 @@
-use clap::Parser;
-use git2::{Error, Repository};
-use semver::Version as SemverVersion;
-use std::process;
-
-#[derive(Parser, Default, Debug)]
-#[command(version, arg_required_else_help = true)]
-/// A CLI app to bump semver tag
-struct Args {
-    #[arg(short = 's', long)]
-    /// The scope of the version: major, minor, or patch
-    scope: Option<String>,
-    #[arg(short = 'o', long)]
-    /// The option to be used: alpha, beta, rc, or just left it empty
-    option: Option<String>,
-    #[arg(short = 'p', long)]
-    /// The prefix to be used: prod, stage, sandbox, dev, etc
-    prefix: Option<String>,
-    #[arg(short = 'd', long, action)]
-    /// Dry run mode, do not create a tag
-    dry_run: bool,
-}
-
-#[derive(Debug, Clone)]
-struct Version {
-    prefix: Option<String>,
-    major: u32,
-    minor: u32,
-    patch: u32,
-    label: Option<String>,
-    rc_number: Option<u32>,
-}
-
-const SCOPE_MAJOR: &str = "major";
-const SCOPE_MINOR: &str = "minor";
-const SCOPE_PATCH: &str = "patch";
-
-const OPT_ALPHA: &str = "alpha";
-const OPT_BETA: &str = "beta";
-const OPT_RC: &str = "rc";
-
-impl Version {
-    fn parse(version: &str) -> Result<Self, String> {
-        let parts: Vec<&str> = version.split('-').collect();
-        let mut prefix_and_version: (Option<String>, &str, Option<String>) = (None, "", None);
-
-        if parts.len() > 2 {
-            for (index, part) in parts.iter().enumerate() {
-                if is_semver(part) {
-                    prefix_and_version = (
-                        Some(parts[..index].join("-")),
-                        part,
-                        Some(parts[index + 1..].join("-")),
-                    );
-                    break;
-                }
-            }
-        } else if parts.len() == 2 {
-            if is_semver(parts[0]) {
-                prefix_and_version = (None, parts[0], Some(parts[1].to_string()));
-            } else {
-                prefix_and_version = (Some(parts[0].to_string()), parts[1], None);
-            }
-        } else if parts.len() == 1 {
-            prefix_and_version = (None, parts[0], None);
+mod cache;
+mod changelog;
+mod cli;
+mod config;
+mod format;
+mod git;
+mod gpg;
+mod hooks;
+mod lock;
+mod logging;
+mod manifest;
+mod printer;
+mod template;
+mod timestamp;
+mod workspace;
+
+use changelog::render_entry;
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
+use cli::{
+    BumpArgs, Cli, Command, CompareArgs, DeleteArgs, DiffArgs, Format, GetArgs, GlobalArgs,
+    ListArgs,
+};
+use config::{render_resolved, Config, Source};
+use format::{render_tag_format, TagFields};
+use git::{
+    annotate_by_default, branch_exists, changed_since, commit_messages_since, commits_since,
+    count_commits_since, create_branch, create_git_tag, delete_git_tag, diff_stats, fetch_tags,
+    get_latest_git_tag, get_version_tags, git_tag_command, has_commits, is_release_commit,
+    is_valid_tag_name, push_tag, remote_only_tags, resolve_head_commit, stage_file, tag_details,
+    tag_exists, tag_points_at_head, TagFilter, TagOptions, DEFAULT_TAG_REF_FORMAT,
+};
+use git2::{Branch, Repository, RepositoryOpenFlags};
+use hooks::run_hook;
+use lock::{write_lock_file, LockEntry, LOCK_FILE};
+use logging::{log_error, log_info, log_warn};
+use manifest::{update_cargo_version, update_package_json, write_version_file};
+use printer::{short_sha, Blocked, BumpResult, Printer};
+use semtag::{detect_scope_from_commits, Version, OPT_RC, SCOPE_AUTO, SCOPE_NONE};
+use std::cmp::Ordering;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+use template::{render_summary, render_tag_message, render_template, render_template_file};
+use workspace::Member;
+
+/// With structured logging `-v` messages are `debug` events and `-vv` ones `trace` events, which
+/// the `SEMTAG_LOG` filter selects instead of `--verbose`
+fn debug(verbose: u8, level: u8, message: &str) {
+    if logging::enabled() {
+        if level <= 1 {
+            tracing::debug!("{}", message);
         } else {
-            return Err("Invalid parts length".to_string());
+            tracing::trace!("{}", message);
         }
+    } else if verbose >= level {
+        eprintln!("[debug] {}", message);
+    }
+}
 
-        let version_parts: Vec<&str> = prefix_and_version.1.split('.').collect();
-        if version_parts.len() < 3 {
-            return Err("Invalid version format".to_string());
-        }
+fn warn(strict: bool, message: &str) {
+    if strict {
+        log_error!("Error: {}", message);
+        process::exit(1);
+    }
+    log_warn!("Warning: {}", message);
+}
 
-        let major = version_parts[0]
-            .parse::<u32>()
-            .map_err(|_| "Invalid major version".to_string())?;
-        let minor = version_parts[1]
-            .parse::<u32>()
-            .map_err(|_| "Invalid minor version".to_string())?;
-        let patch = version_parts[2]
-            .parse::<u32>()
-            .map_err(|_| "Invalid patch version".to_string())?;
+fn check_prefix_series(repo: &Repository, filter: &TagFilter, strict: bool) {
+    let Some(prefix) = filter.prefix.as_deref() else {
+        return;
+    };
+    let unprefixed = TagFilter {
+        prefix: None,
+        ..filter.clone()
+    };
 
-        let label = parts.last().cloned().map(|s| s.to_string());
+    let has_prefixed = get_version_tags(repo, filter, 0).map(|tags| !tags.is_empty());
+    let has_unprefixed = get_version_tags(repo, &unprefixed, 0).map(|tags| !tags.is_empty());
 
-        let rc_number = if let Some(label) = &label {
-            if let Some(stripped) = label.strip_prefix("rc.") {
-                stripped.parse::<u32>().ok()
-            } else {
-                None
+    if let (Ok(false), Ok(true)) = (has_prefixed, has_unprefixed) {
+        warn(
+            strict,
+            &format!(
+                "No tags found with prefix '{}' but unprefixed version tags exist, starting a new '{}' series from 0.0.0",
+                prefix, prefix
+            ),
+        );
+    }
+}
+
+/// The new version must be higher than every existing tag of the series, otherwise it would not
+/// become the latest or collide with a tag created on another branch. Returns the downgrade for
+/// the caller to report, with `allow_downgrade` it is only printed as a warning. Reading the tags
+/// can fail as well, that error is returned as is. With `allow_prerelease_of_latest` a pre-release
+/// of the latest release itself is accepted, that is what `--scope none -o <option>` asks for.
+fn check_downgrade(
+    repo: &Repository,
+    filter: &TagFilter,
+    new_version: &Version,
+    allow_downgrade: bool,
+    allow_prerelease_of_latest: bool,
+    verbose: u8,
+) -> Result<Option<String>, git2::Error> {
+    let tags = get_version_tags(repo, filter, verbose)?;
+    let stages: Vec<&str> = filter.stages.iter().map(String::as_str).collect();
+    let highest = tags
+        .iter()
+        .filter_map(|tag| {
+            let version = Version::parse(filter.version_name(tag).unwrap_or(tag)).ok()?;
+            Some((version, tag))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp_by_stages(b, &stages));
+
+    let Some((highest, tag)) = highest else {
+        return Ok(None);
+    };
+    if highest.cmp_by_stages(new_version, &stages) == Ordering::Less {
+        return Ok(None);
+    }
+    let same_core = (highest.major, highest.minor, highest.patch)
+        == (new_version.major, new_version.minor, new_version.patch);
+    if allow_prerelease_of_latest && !highest.is_prerelease() && same_core {
+        return Ok(None);
+    }
+
+    let message = format!(
+        "'{}' already exists and is not lower than the new version '{}'",
+        tag,
+        filter.tag_name(&new_version.to_string())
+    );
+    if allow_downgrade {
+        log_warn!("Warning: {}", message);
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "{}, pass --allow-downgrade to tag it anyway",
+        message
+    )))
+}
+
+/// `--namespace` is a shorthand for the `refs/tags/<namespace>/{name}` ref format
+fn tag_ref_format(namespace: Option<String>, format: String) -> Option<String> {
+    match namespace {
+        Some(namespace) => Some(format!("refs/tags/{}/{{name}}", namespace)),
+        None => Some(format).filter(|format| format != DEFAULT_TAG_REF_FORMAT),
+    }
+}
+
+fn env_path(key: &str) -> Option<PathBuf> {
+    env::var_os(key)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Opens the repository like git does, `--git-dir` and `-C` take precedence over the `GIT_DIR`
+/// and `GIT_WORK_TREE` environment variables that are set in git hooks
+fn open_repo(global: &GlobalArgs) -> Repository {
+    let path = global.path.as_deref().unwrap_or_else(|| Path::new("."));
+    // Like an explicit --git-dir, -C points at a repository, so the environment of the caller,
+    // e.g. a hook of another repository, must not override it
+    let git_dir = match (&global.git_dir, &global.path) {
+        (Some(git_dir), _) => Some(git_dir.clone()),
+        (None, Some(_)) => None,
+        (None, None) => env_path("GIT_DIR"),
+    };
+    let work_tree = global.path.clone().or_else(|| env_path("GIT_WORK_TREE"));
+
+    let repo = match &git_dir {
+        Some(git_dir) => {
+            Repository::open_ext(git_dir, RepositoryOpenFlags::NO_SEARCH, &[] as &[&OsStr])
+        }
+        None => Repository::open(path),
+    }
+    .and_then(|repo| {
+        if let Some(work_tree) = &work_tree {
+            if git_dir.is_some() || global.path.is_none() {
+                repo.set_workdir(work_tree, false)?;
             }
+        }
+        Ok(repo)
+    });
+
+    match repo {
+        Ok(repo) => repo,
+        Err(e) => {
+            log_error!("'{}' is not a git repository: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+fn resolve_path(global: &GlobalArgs, path: &Path) -> PathBuf {
+    match &global.path {
+        Some(base) if path.is_relative() => base.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// The lock file lives in the root of the working tree, or in the git directory of a bare repository
+fn lock_file_path(repo: &Repository) -> PathBuf {
+    repo.workdir()
+        .unwrap_or_else(|| repo.path())
+        .join(LOCK_FILE)
+}
+
+/// Reads `.semtag.toml` from the root of the working tree, or from the git directory of a bare
+/// repository
+fn load_config(repo: &Repository) -> Config {
+    let path = config::config_path(repo.workdir().unwrap_or_else(|| repo.path()));
+    match config::load(&path) {
+        Ok(config) => config,
+        Err(e) => {
+            log_error!("Error reading the config: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// The bump settings that are resolved from more than the command line
+struct Resolved<'a> {
+    scope: Option<&'a str>,
+    option: Option<&'a str>,
+    prefix: Option<&'a str>,
+    annotate: bool,
+}
+
+/// The settings printed by --config-show, with the source each value was resolved from
+fn resolved_settings(
+    args: &BumpArgs,
+    matches: &ArgMatches,
+    repo: &Repository,
+    config: &Config,
+    resolved: &Resolved,
+) -> Vec<(&'static str, toml_edit::Value, Source)> {
+    let source = |id: &str| Source::of(matches, id);
+    let mut settings = Vec::new();
+
+    let strings = [
+        ("scope", resolved.scope),
+        ("option", resolved.option),
+        ("pattern", args.pattern.as_deref()),
+        ("namespace", args.namespace.as_deref()),
+        ("message", args.message.as_deref()),
+        ("message_template", args.message_template.as_deref()),
+        ("tag_message_format", args.tag_message_format.as_deref()),
+        ("pre_run_hook", args.pre_run_hook.as_deref()),
+        ("pre_push_hook", args.pre_push_hook.as_deref()),
+        ("post_run_hook", args.post_run_hook.as_deref()),
+        ("post_tag_command", args.post_tag_command.as_deref()),
+    ];
+    for (key, value) in strings {
+        if let Some(value) = value {
+            settings.push((key, value.into(), source(key)));
+        }
+    }
+
+    if let Some(prefix) = resolved.prefix {
+        let prefix_source = match source("prefix") {
+            Source::Default if config.prefix.is_some() => Source::ConfigFile,
+            prefix_source => prefix_source,
+        };
+        settings.push(("prefix", prefix.into(), prefix_source));
+    }
+
+    // The first of the settings that turn annotated tags on or off decides
+    let annotate_flags = [
+        ("lightweight", args.lightweight),
+        ("annotate", args.annotate),
+        ("annotate_from_changelog", args.annotate_from_changelog),
+        ("tag_message_format", args.tag_message_format.is_some()),
+        ("template_file", args.template_file.is_some()),
+    ];
+    let git_config_annotate = repo
+        .config()
+        .and_then(|git_config| git_config.get_bool("semtag.annotate"))
+        .is_ok();
+    let annotate_source = match annotate_flags.into_iter().find(|(_, set)| *set) {
+        Some((id, _)) => source(id),
+        None if config.annotate.is_some() => Source::ConfigFile,
+        None if git_config_annotate => Source::GitConfig,
+        None => Source::Default,
+    };
+    settings.push(("annotate", resolved.annotate.into(), annotate_source));
+
+    let paths = [
+        ("tag_body_file", args.tag_body_file.as_deref()),
+        ("template_file", args.template_file.as_deref()),
+    ];
+    for (key, path) in paths {
+        if let Some(path) = path {
+            settings.push((key, path.display().to_string().into(), source(key)));
+        }
+    }
+    if let Some(key) = &args.sign {
+        let sign: toml_edit::Value = if key.is_empty() {
+            true.into()
         } else {
-            None
+            key.as_str().into()
         };
+        settings.push(("sign", sign, source("sign")));
+    }
 
-        Ok(Self {
-            prefix: prefix_and_version.0,
-            major,
-            minor,
-            patch,
-            label,
-            rc_number,
-        })
+    let allowed_options: toml_edit::Array =
+        args.allowed_options.iter().map(String::as_str).collect();
+    settings.push((
+        "allowed_options",
+        allowed_options.into(),
+        source("allowed_options"),
+    ));
+    settings.push(("remote", args.remote.as_str().into(), source("remote")));
+    settings.push(("retries", i64::from(args.retries).into(), source("retries")));
+    settings.push(("fetch", args.fetch.into(), source("fetch")));
+    settings.push(("push", args.push.into(), source("push")));
+
+    settings
+}
+
+/// The build metadata of --build and --build-timestamp, joined with a dot when both are given,
+/// e.g. ci.42.20240615T101500Z. The clock is passed in as seconds since the epoch.
+fn build_metadata(build: Option<&str>, now: Option<i64>) -> Option<String> {
+    let timestamp = now.map(timestamp::format_compact);
+    match (build, timestamp) {
+        (Some(build), Some(timestamp)) => Some(format!("{}.{}", build, timestamp)),
+        (build, timestamp) => build.map(str::to_string).or(timestamp),
+    }
+}
+
+/// Reads the tag message from the file, without the trailing newlines
+fn read_tag_body(path: &Path) -> String {
+    match fs::read_to_string(path) {
+        Ok(body) => body.trim_end_matches(['\r', '\n']).to_string(),
+        Err(e) => {
+            log_error!("Error reading '{}': {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Expands a `--template-file` into the tag message, without the trailing newlines
+fn read_template_file(path: &Path, version: &Version, previous: &str) -> String {
+    let template = match fs::read_to_string(path) {
+        Ok(template) => template,
+        Err(e) => {
+            log_error!("Error reading template file '{}': {}", path.display(), e);
+            process::exit(1);
+        }
+    };
+    let date = timestamp::format_date(timestamp::now());
+    match render_template_file(&template, version, previous, &date) {
+        Ok(message) => message.trim_end_matches(['\r', '\n']).to_string(),
+        Err(e) => {
+            log_error!("Error in template file '{}': {}", path.display(), e);
+            process::exit(1);
+        }
     }
+}
 
-    fn increment(&self, scope: Option<&str>, option: Option<&str>) -> Result<Self, String> {
-        let mut new_version = self.clone();
+fn update_version_file(
+    repo: &Repository,
+    path: &Path,
+    stage: bool,
+    printer: &Printer,
+    update: impl FnOnce(&Path) -> Result<(), String>,
+) {
+    if let Err(e) = update(path) {
+        log_error!("Error updating version file: {}", e);
+        process::exit(1);
+    }
+    printer.status(&format!("Version updated in '{}'", path.display()));
 
-        match scope {
-            Some(SCOPE_MAJOR) => {
-                new_version.major += 1;
-                new_version.minor = 0;
-                new_version.patch = 0;
-                new_version.label = None;
+    if stage {
+        if let Err(e) = stage_file(repo, path) {
+            log_error!("Error staging '{}': {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+fn run_pre_run_hook(hook: &str, envs: &[(&str, &str)]) {
+    match run_hook(hook, envs) {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            log_error!("Pre-run hook failed ({}), the tag was not created", status);
+            process::exit(status.code().unwrap_or(1));
+        }
+        Err(e) => {
+            log_error!("Error running pre-run hook: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn run_pre_push_hook(hook: &str, envs: &[(&str, &str)], tag: &str) {
+    match run_hook(hook, envs) {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            log_error!(
+                "Pre-push hook failed ({}), tag '{}' was kept locally but not pushed",
+                status,
+                tag
+            );
+            process::exit(status.code().unwrap_or(1));
+        }
+        Err(e) => {
+            log_error!("Error running pre-push hook: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn run_post_run_hook(hook: &str, envs: &[(&str, &str)], tag: &str) {
+    let failure = match run_hook(hook, envs) {
+        Ok(status) if status.success() => return,
+        Ok(status) => status.to_string(),
+        Err(e) => e.to_string(),
+    };
+    log_warn!(
+        "Warning: post-run hook failed ({}), tag '{}' was kept and may need manual cleanup",
+        failure,
+        tag
+    );
+}
+
+fn run_post_tag_command(command: &str, tag: &str) {
+    match run_hook(command, &[("SEMTAG_NEW_TAG", tag)]) {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            log_error!("Post-tag command failed ({})", status);
+            process::exit(status.code().unwrap_or(1));
+        }
+        Err(e) => {
+            log_error!("Error running post-tag command: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn workspace_members(global: &GlobalArgs, manifest: &Path) -> Vec<Member> {
+    match workspace::members(&resolve_path(global, manifest)) {
+        Ok(members) => members,
+        Err(e) => {
+            log_error!("Error reading the workspace: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Bumps every workspace member in turn, with the crate name as the prefix and its own manifest
+/// for --update-cargo-version
+fn bump_workspace(args: BumpArgs, global: &GlobalArgs, matches: &ArgMatches) {
+    for member in workspace_members(global, &args.cargo_manifest) {
+        debug(
+            global.verbose,
+            1,
+            &format!("Bumping workspace member '{}'", member.name),
+        );
+        let member_args = BumpArgs {
+            prefix: Some(member.name),
+            cargo_manifest: member.manifest,
+            workspace: false,
+            ..args.clone()
+        };
+        bump(member_args, global, matches);
+    }
+}
+
+fn bump(args: BumpArgs, global: &GlobalArgs, matches: &ArgMatches) {
+    if args.workspace {
+        bump_workspace(args, global, matches);
+        return;
+    }
+
+    let verbose = global.verbose;
+    let scope = args.scope.clone().filter(|scope| scope != SCOPE_NONE);
+    let option = args.option.clone();
+    let prefix = args.prefix.clone();
+    let dry_run = args.dry_run;
+    let strict = args.strict;
+    let printer = Printer::new(args.format, global.color, global.no_color);
+
+    if let Some(option) = option.as_deref() {
+        if !args.allowed_options.iter().any(|allowed| allowed == option) {
+            log_error!(
+                "Error: invalid value '{}' for '--option <OPTION>', allowed options are: {}",
+                option,
+                args.allowed_options.join(", ")
+            );
+            process::exit(1);
+        }
+    }
+
+    if scope.is_none()
+        && option.is_none()
+        && !args.prerelease_only
+        && !args.show_next
+        && !args.config_show
+    {
+        log_error!("Error: nothing to bump, specify --scope or --option");
+        process::exit(1);
+    }
+
+    let repo = open_repo(global);
+    let config = load_config(&repo);
+    let annotate = if args.lightweight {
+        false
+    } else {
+        args.annotate
+            || args.annotate_from_changelog
+            || args.tag_message_format.is_some()
+            || args.template_file.is_some()
+            || config
+                .annotate
+                .unwrap_or_else(|| annotate_by_default(&repo))
+    };
+    let prefix = prefix.or(config.prefix.clone().filter(|_| args.pattern.is_none()));
+
+    if args.config_show {
+        let resolved = Resolved {
+            scope: scope.as_deref(),
+            option: option.as_deref(),
+            prefix: prefix.as_deref(),
+            annotate,
+        };
+        print!(
+            "{}",
+            render_resolved(&resolved_settings(
+                &args, matches, &repo, &config, &resolved
+            ))
+        );
+        return;
+    }
+
+    let has_commits = match has_commits(&repo) {
+        Ok(has_commits) => has_commits,
+        Err(e) => {
+            log_error!("Error resolving HEAD: {}", e);
+            process::exit(1);
+        }
+    };
+    if !has_commits && !dry_run && !args.summary && !args.changelog_entry {
+        log_error!(
+            "Error: the repository has no commits yet, a tag can't be created before the first commit (use --dry-run to preview the version)"
+        );
+        process::exit(1);
+    }
+
+    if args.fetch {
+        if let Err(e) = fetch_tags(&repo, &args.remote, args.retries, verbose) {
+            log_error!("Error fetching tags from '{}': {}", args.remote, e);
+            process::exit(1);
+        }
+        debug(verbose, 1, &format!("Fetched tags from '{}'", args.remote));
+    }
+
+    let filter = TagFilter {
+        ref_format: tag_ref_format(args.namespace, args.tag_ref_format),
+        prefix,
+        pattern: args.pattern,
+        annotated_only: args.annotated_only,
+        include: args.include,
+        exclude: args.exclude,
+        max_tags: args.max_tags,
+        sort: args.tag_sort,
+        cache_file: args.cache_file.map(|path| resolve_path(global, &path)),
+        stages: args.allowed_options.clone(),
+    };
+
+    if args.skip_if_release_commit {
+        match is_release_commit(&repo, &args.release_commit_pattern) {
+            Ok(true) => {
+                printer.status("Skipping: release commit detected");
+                return;
             }
-            Some(SCOPE_MINOR) => {
-                new_version.minor += 1;
-                new_version.patch = 0;
-                new_version.label = None;
+            Ok(false) => {}
+            Err(e) => {
+                log_error!("Error reading the HEAD commit: {}", e);
+                process::exit(1);
             }
-            Some(SCOPE_PATCH) => {
-                new_version.patch += 1;
-                new_version.label = None;
+        }
+    }
+
+    let current_version = if let Some(base_tag) = args.base_tag {
+        match tag_exists(&repo, &base_tag) {
+            Ok(true) => {}
+            Ok(false) => {
+                log_error!("Error: base tag '{}' does not exist", base_tag);
+                process::exit(1);
             }
-            None => {}
-            _ => {
-                return Err(
-                    "Invalid scope. Valid scopes are: major, minor, patch, and option".to_string(),
-                )
+            Err(e) => {
+                log_error!("Error looking up base tag '{}': {}", base_tag, e);
+                process::exit(1);
             }
         }
+        debug(verbose, 1, &format!("Using base tag '{}'", base_tag));
+        base_tag
+    } else {
+        check_prefix_series(&repo, &filter, strict);
 
-        match option {
-            Some(OPT_ALPHA) => {
-                new_version.label = Some(OPT_ALPHA.to_string());
-                new_version.rc_number = None;
+        match get_latest_git_tag(&repo, &filter, option.as_deref(), verbose) {
+            Ok(tag) => tag,
+            Err(e) => {
+                log_error!("Error fetching latest tag: {}", e);
+                process::exit(1);
             }
-            Some(OPT_BETA) => {
-                new_version.label = Some(OPT_BETA.to_string());
-                new_version.rc_number = None;
+        }
+    };
+
+    if args.require_commits {
+        match count_commits_since(&repo, &current_version, args.first_parent) {
+            Ok(0) => {
+                log_error!(
+                    "Error: no new commits since '{}', nothing to release",
+                    current_version
+                );
+                process::exit(1);
             }
-            Some(OPT_RC) => {
-                let new_rc_number = new_version.rc_number.unwrap_or(0) + 1;
-                let new_label = format!("{}.{}", OPT_RC, new_rc_number);
+            Ok(count) => debug(
+                verbose,
+                1,
+                &format!("{} commit(s) since '{}'", count, current_version),
+            ),
+            Err(e) => {
+                log_error!("Error counting commits since '{}': {}", current_version, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    match tag_points_at_head(&repo, &current_version) {
+        Ok(true) => warn(
+            strict,
+            &format!(
+                "HEAD is already tagged as '{}', the new tag would release the same commit",
+                current_version
+            ),
+        ),
+        Ok(false) => {}
+        Err(e) => debug(
+            verbose,
+            1,
+            &format!("Could not compare HEAD with '{}': {}", current_version, e),
+        ),
+    }
 
-                new_version.rc_number = Some(new_rc_number);
-                new_version.label = Some(new_label);
+    if let Some(path) = &args.only_if_changed {
+        match changed_since(&repo, &current_version, path) {
+            Ok(true) => {}
+            Ok(false) => {
+                printer.status(&format!(
+                    "No changes in {} since {}; skipping.",
+                    path.display(),
+                    current_version
+                ));
+                return;
             }
-            None => {}
-            _ => {
-                return Err(
-                    "Invalid option. Valid scopes are: alpha, beta, rc, or just left it empty"
-                        .to_string(),
+            Err(e) => {
+                log_error!(
+                    "Error diffing '{}' since '{}': {}",
+                    path.display(),
+                    current_version,
+                    e
                 );
+                process::exit(1);
             }
         }
+    }
 
-        Ok(new_version)
+    if verbose > 0 {
+        match repo.head().ok().and_then(|head| head.target()) {
+            Some(oid) => debug(verbose, 1, &format!("HEAD resolves to {}", oid)),
+            None => debug(verbose, 1, "HEAD does not resolve to a commit"),
+        }
     }
-}
 
-impl std::fmt::Display for Version {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let version = format!("{}.{}.{}", self.major, self.minor, self.patch);
-        let label = if let Some(label) = &self.label {
-            if label == OPT_RC {
-                format!("-rc.{}", self.rc_number.unwrap_or(0))
+    let scope = match scope.as_deref() {
+        Some(SCOPE_AUTO) => {
+            let messages = match commit_messages_since(&repo, &current_version, args.first_parent) {
+                Ok(messages) => messages,
+                Err(e) => {
+                    log_error!(
+                        "Error reading the commits since '{}': {}",
+                        current_version,
+                        e
+                    );
+                    process::exit(1);
+                }
+            };
+            let detected = detect_scope_from_commits(messages.iter().map(String::as_str));
+            debug(
+                verbose,
+                1,
+                &format!(
+                    "Detected scope '{}' from {} commits since '{}'",
+                    detected,
+                    messages.len(),
+                    current_version
+                ),
+            );
+            Some(detected.to_string())
+        }
+        _ => scope,
+    };
+
+    match Version::parse(
+        filter
+            .version_name(&current_version)
+            .unwrap_or(&current_version),
+    ) {
+        Ok(version) => {
+            let allowed_options: Vec<&str> =
+                args.allowed_options.iter().map(String::as_str).collect();
+            if args.show_next {
+                let next: Vec<(String, String)> = version
+                    .next_versions(&allowed_options)
+                    .into_iter()
+                    .map(|(arguments, next)| (arguments, filter.tag_name(&next.to_string())))
+                    .collect();
+                printer.next_versions(&current_version, &next);
+                return;
+            }
+            let new_version = if args.prerelease_only {
+                version.bump_prerelease()
             } else {
-                format!("-{}", label)
+                version.increment_from(scope.as_deref(), option.as_deref(), &allowed_options)
+            };
+            let mut new_version = match new_version {
+                Ok(new_version) => new_version,
+                Err(e) => {
+                    log_error!("Error incrementing version: {}", e);
+                    process::exit(1);
+                }
+            };
+
+            if let Some(rc_number) = args.rc_number {
+                if option.as_deref() != Some(OPT_RC) {
+                    log_error!("Error: --rc-number requires --option rc");
+                    process::exit(1);
+                }
+                if let Some(existing) = version.rc_number {
+                    if version.promote() == new_version.promote() && rc_number <= existing {
+                        warn(
+                            strict,
+                            &format!(
+                                "rc.{} is not greater than the existing rc.{} of {}",
+                                rc_number, existing, current_version
+                            ),
+                        );
+                    }
+                }
+                new_version = new_version.with_rc_number(rc_number);
             }
-        } else {
-            String::new()
-        };
-        let prefix = if let Some(prefix) = &self.prefix {
-            format!("{}-", prefix)
-        } else {
-            String::new()
-        };
 
-        write!(f, "{}{}{}", prefix, version, label)
-    }
-}
+            if let Some(min_version) = &args.min_version {
+                // The floor belongs to the same series, so it takes the prefix of the new version
+                let min_version = Version {
+                    prefix: new_version.prefix.clone(),
+                    ..min_version.clone()
+                };
+                if new_version < min_version {
+                    log_info!(
+                        "Note: {} is below the minimum version, using {} instead",
+                        new_version,
+                        min_version
+                    );
+                    new_version = min_version;
+                }
+            }
 
-fn is_semver(version_str: &str) -> bool {
-    SemverVersion::parse(version_str).is_ok()
-}
+            if args.strict_prerelease_order {
+                if let Err(e) = version.check_stage_order(&new_version) {
+                    log_error!("Error: {}", e);
+                    process::exit(1);
+                }
+            }
 
-fn get_latest_git_tag(
-    repo: &Repository,
-    prefix: Option<&str>,
-    option: Option<&str>,
-) -> Result<String, Error> {
-    let tags = repo.tag_names(None)?;
+            let build = build_metadata(
+                args.build.as_deref(),
+                args.build_timestamp.then(timestamp::now),
+            );
+            if let Some(build) = build {
+                new_version = match new_version.with_build(&build) {
+                    Ok(new_version) => new_version,
+                    Err(e) => {
+                        log_error!("Error: {}", e);
+                        process::exit(1);
+                    }
+                };
+            }
 
-    let filtered_tags: Vec<&str> = tags
-        .iter()
-        .flatten()
-        .filter(|tag| {
-            if let Some(prefix) = prefix {
-                tag.starts_with(prefix)
+            let new_version_str = filter.tag_name(&new_version.to_string());
+
+            if !is_valid_tag_name(&new_version_str) {
+                log_error!("Error: '{}' is not a valid git tag name", new_version_str);
+                process::exit(1);
+            }
+
+            if args.summary {
+                let reasons: Vec<&str> = if args.prerelease_only {
+                    vec!["pre-release"]
+                } else {
+                    scope
+                        .iter()
+                        .chain(option.iter())
+                        .map(String::as_str)
+                        .collect()
+                };
+                println!("{}", render_summary(&version, &new_version, &reasons));
+                return;
+            }
+
+            if args.changelog_entry {
+                match commits_since(&repo, &current_version, args.first_parent) {
+                    Ok(commits) => print!(
+                        "{}",
+                        render_entry(
+                            &new_version_str,
+                            &timestamp::format_date(timestamp::now()),
+                            &commits
+                        )
+                    ),
+                    Err(e) => {
+                        log_error!("Error reading commits since '{}': {}", current_version, e);
+                        process::exit(1);
+                    }
+                }
+                return;
+            }
+
+            // Without a scope, `-s none -o alpha` and `-o alpha` alike ask for a pre-release of the
+            // current version, which is lower than the release by design but not than anything else
+            let allow_prerelease_of_latest = scope.is_none() && !args.prerelease_only;
+            // A machine-readable dry run reports why the tag would not be created instead of failing
+            let report_blocked = dry_run && printer.format() == Format::Json;
+            let mut blocked = None;
+            match check_downgrade(
+                &repo,
+                &filter,
+                &new_version,
+                args.allow_downgrade,
+                allow_prerelease_of_latest,
+                verbose,
+            ) {
+                Ok(None) => {}
+                Ok(Some(message)) if report_blocked => {
+                    blocked = Some(Blocked {
+                        reason: "downgrade",
+                        message,
+                    });
+                }
+                Ok(Some(message)) => {
+                    log_error!("Error: {}", message);
+                    process::exit(1);
+                }
+                Err(e) => {
+                    log_error!("Error fetching tags: {}", e);
+                    process::exit(1);
+                }
+            }
+            if report_blocked && !has_commits {
+                blocked = Some(Blocked {
+                    reason: "no-commits",
+                    message: "the repository has no commits yet".to_string(),
+                });
+            }
+            if dry_run && tag_exists(&repo, &new_version_str).unwrap_or(false) {
+                blocked = Some(Blocked {
+                    reason: "tag-exists",
+                    message: format!("tag '{}' already exists", new_version_str),
+                });
+            }
+
+            let branch = args
+                .create_branch
+                .as_ref()
+                .map(|template| render_template(template, &new_version, &current_version));
+            if let Some(branch) = &branch {
+                if !Branch::name_is_valid(branch).unwrap_or(false) {
+                    log_error!("Error: '{}' is not a valid git branch name", branch);
+                    process::exit(1);
+                }
+                match branch_exists(&repo, branch) {
+                    Ok(true) if !args.force => {
+                        log_error!(
+                            "Error: branch '{}' already exists, pass --force to overwrite it",
+                            branch
+                        );
+                        process::exit(1);
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log_error!("Error looking up branch '{}': {}", branch, e);
+                        process::exit(1);
+                    }
+                }
+            }
+
+            let message = match (args.message, args.message_template, &args.tag_body_file) {
+                (Some(message), _, _) => Some(message),
+                (None, Some(template), _) => {
+                    Some(render_template(&template, &new_version, &current_version))
+                }
+                (None, None, Some(path)) => Some(read_tag_body(&resolve_path(global, path))),
+                (None, None, None) if args.annotate_from_changelog => {
+                    match commits_since(&repo, &current_version, args.first_parent) {
+                        Ok(commits) => Some(render_entry(
+                            &new_version_str,
+                            &timestamp::format_date(timestamp::now()),
+                            &commits,
+                        )),
+                        Err(e) => {
+                            log_error!("Error reading commits since '{}': {}", current_version, e);
+                            process::exit(1);
+                        }
+                    }
+                }
+                (None, None, None) if args.template_file.is_some() => {
+                    args.template_file.as_deref().map(|path| {
+                        read_template_file(
+                            &resolve_path(global, path),
+                            &new_version,
+                            &current_version,
+                        )
+                    })
+                }
+                (None, None, None) if args.tag_message_format.is_some() => {
+                    let format = args.tag_message_format.as_deref().unwrap_or_default();
+                    match commits_since(&repo, &current_version, args.first_parent) {
+                        Ok(commits) => Some(render_tag_message(
+                            format,
+                            &new_version,
+                            &current_version,
+                            scope.as_deref().unwrap_or(SCOPE_NONE),
+                            &commits,
+                        )),
+                        Err(e) => {
+                            log_error!("Error reading commits since '{}': {}", current_version, e);
+                            process::exit(1);
+                        }
+                    }
+                }
+                (None, None, None) if annotate || args.sign.is_some() => {
+                    Some(new_version_str.clone())
+                }
+                (None, None, None) => None,
+            };
+
+            let sign_key = args.sign.as_ref().filter(|key| !key.is_empty()).cloned();
+            if args.sign.is_some() && sign_key.is_none() {
+                log_warn!("Warning: no key ID given, gpg will sign with its default key");
+            }
+
+            let options = TagOptions {
+                message,
+                sign: args.sign.is_some(),
+                sign_key,
+                gpg_program: args.gpg_program,
+            };
+
+            if dry_run {
+                let head = repo
+                    .head()
+                    .ok()
+                    .and_then(|head| head.target())
+                    .map(|oid| oid.to_string());
+                if printer.format() != Format::Text {
+                    let result = BumpResult {
+                        previous: &current_version,
+                        tag: &new_version_str,
+                        version: new_version.without_prefix().to_string(),
+                        target_sha: head.as_deref(),
+                        dry_run: true,
+                        no_tag: false,
+                        blocked,
+                    };
+                    printer.result(&result);
+                    return;
+                }
+                let target = head.unwrap_or_else(|| "HEAD".to_string());
+
+                printer.dry_run(&current_version, &new_version_str);
+                if let Some(blocked) = &blocked {
+                    printer.status(&format!("Blocked       : {}", blocked.message));
+                }
+                if !has_commits {
+                    printer.status(
+                        "Note          : the repository has no commits yet, the tag can't be created before the first commit",
+                    );
+                }
+                printer.status(&format!(
+                    "Git command   : {}",
+                    git_tag_command(&new_version_str, &options, &target)
+                ));
+                if let Some(hook) = &args.pre_run_hook {
+                    printer.status(&format!("Pre-run hook  : {}", hook));
+                }
+                if args.annotate_from_changelog
+                    || args.tag_message_format.is_some()
+                    || args.template_file.is_some()
+                {
+                    if let Some(message) = &options.message {
+                        printer.status(&format!("Tag message   :\n{}", message.trim_end()));
+                    }
+                }
+                if let Some(branch) = &branch {
+                    printer.status(&format!("Would create  : branch '{}'", branch));
+                }
+                if let Some(hook) = &args.pre_push_hook {
+                    printer.status(&format!("Pre-push hook : {}", hook));
+                }
+                if args.push {
+                    printer.status(&format!(
+                        "Would push    : tag '{}' to '{}'",
+                        new_version_str, args.remote
+                    ));
+                }
+                if args.update_cargo_version {
+                    let manifest = resolve_path(global, &args.cargo_manifest);
+                    printer.status(&format!("Would update  : '{}'", manifest.display()));
+                }
+                if args.update_package_json {
+                    let package_json = resolve_path(global, &args.package_json);
+                    printer.status(&format!("Would update  : '{}'", package_json.display()));
+                }
+                if let Some(version_file) = &args.update_version_file {
+                    let version_file = resolve_path(global, version_file);
+                    printer.status(&format!("Would update  : '{}'", version_file.display()));
+                }
+                if !args.no_lock {
+                    printer.status(&format!(
+                        "Would update  : '{}'",
+                        lock_file_path(&repo).display()
+                    ));
+                }
+                if let Some(hook) = &args.post_run_hook {
+                    printer.status(&format!("Post-run hook : {}", hook));
+                }
+                if let Some(command) = &args.post_tag_command {
+                    printer.status(&format!("Would run     : {}", command));
+                }
+                return;
+            }
+
+            let hook_envs = [
+                ("SEMTAG_NEW_VERSION", new_version_str.as_str()),
+                ("SEMTAG_PREVIOUS_VERSION", current_version.as_str()),
+            ];
+            if let Some(hook) = &args.pre_run_hook {
+                run_pre_run_hook(hook, &hook_envs);
+            }
+
+            let target = if args.no_tag {
+                // The checks of the tag creation itself still apply, so --no-tag fails where the
+                // real run would
+                if tag_exists(&repo, &new_version_str).unwrap_or(false) {
+                    log_error!(
+                        "Error creating tag: tag '{}' already exists",
+                        new_version_str
+                    );
+                    process::exit(1);
+                }
+                match resolve_head_commit(&repo) {
+                    Ok(commit) => commit.id(),
+                    Err(e) => {
+                        log_error!("Error creating tag: {}", e);
+                        process::exit(1);
+                    }
+                }
+            } else {
+                match create_git_tag(&repo, &new_version_str, &options) {
+                    Ok(oid) => oid,
+                    Err(e) => {
+                        log_error!("Error creating tag: {}", e);
+                        process::exit(1);
+                    }
+                }
+            };
+            let target_sha = target.to_string();
+            if args.no_tag {
+                printer.status(&format!(
+                    "Skipped tag '{}' on commit {} ({}) because of --no-tag",
+                    new_version_str,
+                    short_sha(&target_sha),
+                    target_sha
+                ));
             } else {
-                is_semver(tag.split('-').collect::<Vec<&str>>()[0])
+                debug(verbose, 1, &format!("Tagged commit {}", target_sha));
+                printer.status(&format!(
+                    "Tag '{}' created successfully on commit {} ({})",
+                    new_version_str,
+                    short_sha(&target_sha),
+                    target_sha
+                ));
+            }
+
+            if let Some(branch) = &branch {
+                if let Err(e) = create_branch(&repo, branch, target, args.force) {
+                    log_error!("Error creating branch '{}': {}", branch, e);
+                    process::exit(1);
+                }
+                printer.status(&format!("Branch '{}' created successfully", branch));
+            }
+
+            // Without a tag there is nothing to push
+            if args.push && !args.no_tag {
+                if let Some(hook) = &args.pre_push_hook {
+                    let push_envs = [
+                        ("SEMTAG_REMOTE", args.remote.as_str()),
+                        ("SEMTAG_TAG_NAME", new_version_str.as_str()),
+                        ("SEMTAG_COMMIT", target_sha.as_str()),
+                    ];
+                    run_pre_push_hook(hook, &push_envs, &new_version_str);
+                }
+                if let Err(e) =
+                    push_tag(&repo, &args.remote, &new_version_str, args.retries, verbose)
+                {
+                    log_error!(
+                        "Error pushing tag '{}' to '{}': {}, the local tag was kept",
+                        new_version_str,
+                        args.remote,
+                        e
+                    );
+                    process::exit(1);
+                }
+                printer.status(&format!(
+                    "Tag '{}' pushed to '{}'",
+                    new_version_str, args.remote
+                ));
             }
-        })
-        .collect();
 
-    if filtered_tags.is_empty() {
-        let mut tag = "0.0.0".to_string();
-        if let Some(prefix) = prefix {
-            tag = format!("{}-{}", prefix, tag);
+            let file_version = new_version.without_prefix().to_string();
+            if args.update_cargo_version {
+                let manifest = resolve_path(global, &args.cargo_manifest);
+                update_version_file(&repo, &manifest, args.stage, &printer, |path| {
+                    update_cargo_version(path, &file_version)
+                });
+            }
+            if args.update_package_json {
+                let package_json = resolve_path(global, &args.package_json);
+                update_version_file(&repo, &package_json, args.stage, &printer, |path| {
+                    update_package_json(path, &file_version)
+                });
+            }
+            if let Some(version_file) = &args.update_version_file {
+                let version_file = resolve_path(global, version_file);
+                update_version_file(&repo, &version_file, args.stage, &printer, |path| {
+                    write_version_file(path, &file_version)
+                });
+            }
+
+            if !args.no_lock {
+                let entry = LockEntry {
+                    previous: &current_version,
+                    current: &new_version_str,
+                    commit: target_sha.clone(),
+                    scope: scope.as_deref().unwrap_or(SCOPE_NONE),
+                };
+                let lock_file = lock_file_path(&repo);
+                update_version_file(&repo, &lock_file, args.stage, &printer, |path| {
+                    write_lock_file(path, &entry)
+                });
+            }
+
+            let result = BumpResult {
+                previous: &current_version,
+                tag: &new_version_str,
+                version: file_version,
+                target_sha: Some(&target_sha),
+                dry_run: false,
+                no_tag: args.no_tag,
+                blocked: None,
+            };
+            printer.result(&result);
+
+            if let Some(hook) = &args.post_run_hook {
+                run_post_run_hook(hook, &hook_envs, &new_version_str);
+            }
+            if let Some(command) = &args.post_tag_command {
+                run_post_tag_command(command, &new_version_str);
+            }
         }
-        if let Some(option) = option {
-            tag = format!("{}-{}", tag, option);
+        Err(err) => {
+            log_error!("Error: {}", err);
+            process::exit(1);
         }
-        Ok(tag.to_string())
-    } else {
-        let tag = filtered_tags.last().unwrap().to_string();
-        Ok(tag.to_string())
     }
 }
 
-fn create_git_tag(repo: &Repository, tag: &str) -> Result<(), Error> {
-    let reference = repo
-        .head()?
-        .resolve()?
-        .target()
-        .ok_or_else(|| Error::from_str("Cannot resolve HEAD"))?;
-    let commit = repo.find_commit(reference)?;
-    let object = commit.as_object();
+fn get(args: GetArgs, global: &GlobalArgs) {
+    let verbose = global.verbose;
+    let repo = open_repo(global);
+    let config = load_config(&repo);
+    let filter = TagFilter {
+        ref_format: tag_ref_format(args.namespace, args.tag_ref_format),
+        prefix: args
+            .prefix
+            .or(config.prefix.filter(|_| args.pattern.is_none())),
+        pattern: args.pattern,
+        include: args.include,
+        exclude: args.exclude,
+        max_tags: args.max_tags,
+        sort: args.tag_sort,
+        cache_file: args.cache_file.map(|path| resolve_path(global, &path)),
+        ..TagFilter::default()
+    };
 
-    repo.tag_lightweight(tag, object, false)?;
-    println!("Tag '{}' created successfully", tag);
+    if args.workspace {
+        for member in workspace_members(global, &args.cargo_manifest) {
+            let filter = TagFilter {
+                prefix: Some(member.name.clone()),
+                ..filter.clone()
+            };
+            match get_version_tags(&repo, &filter, verbose) {
+                Ok(tags) => println!(
+                    "{}: {}",
+                    member.name,
+                    latest_tag(&filter, &tags, args.latest_stable, args.latest_prerelease)
+                        .map_or("no version tags found", String::as_str)
+                ),
+                Err(e) => {
+                    log_error!("Error fetching tags for '{}': {}", member.name, e);
+                    process::exit(1);
+                }
+            }
+        }
+        return;
+    }
 
-    Ok(())
+    match get_version_tags(&repo, &filter, verbose) {
+        Ok(tags) => match latest_tag(&filter, &tags, args.latest_stable, args.latest_prerelease) {
+            Some(tag) => println!("{}", tag),
+            None if args.latest_stable => {
+                log_error!("No stable version tags found");
+                process::exit(1);
+            }
+            None if args.latest_prerelease => {
+                log_error!("No pre-release version tags found");
+                process::exit(1);
+            }
+            None => {
+                log_error!("No version tags found");
+                process::exit(1);
+            }
+        },
+        Err(e) => {
+            log_error!("Error fetching tags: {}", e);
+            process::exit(1);
+        }
+    }
 }
 
-fn main() {
-    let args = Args::parse();
+/// The last of the sorted tags, or with `--latest-stable` and `--latest-prerelease` the highest
+/// version without, respectively with, a pre-release label
+fn latest_tag<'a>(
+    filter: &TagFilter,
+    tags: &'a [String],
+    stable: bool,
+    prerelease: bool,
+) -> Option<&'a String> {
+    if !stable && !prerelease {
+        return tags.last();
+    }
 
-    let scope = args.scope;
-    let option = args.option;
-    let prefix = args.prefix;
-    let dry_run = args.dry_run;
+    tags.iter()
+        .filter_map(|tag| {
+            let version = Version::parse(filter.version_name(tag).unwrap_or(tag)).ok()?;
+            (version.is_prerelease() == prerelease).then_some((version, tag))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tag)| tag)
+}
 
-    let repo = match Repository::open(".") {
-        Ok(repo) => repo,
+/// The `list --format` line of a tag
+fn tag_line(repo: &Repository, filter: &TagFilter, tag: &str, format: &str) -> String {
+    let details = match tag_details(repo, tag) {
+        Ok(details) => details,
         Err(e) => {
-            eprintln!("The current directory is not a git repository: {}", e);
+            log_error!("Error reading tag '{}': {}", tag, e);
             process::exit(1);
         }
     };
+    let name = filter.version_name(tag).unwrap_or(tag);
+    let version = Version::parse(name).ok();
+    let fields = TagFields {
+        name: tag,
+        version: version.as_ref().map_or_else(
+            || name.to_string(),
+            |version| version.without_prefix().to_string(),
+        ),
+        prefix: version
+            .as_ref()
+            .and_then(|version| version.prefix.as_deref())
+            .unwrap_or_default(),
+        commit: details.commit.to_string(),
+        date: timestamp::format_rfc3339(details.created),
+        tagger: details.tagger.as_deref().unwrap_or_default(),
+    };
+
+    render_tag_format(format, &fields)
+}
 
-    let current_version = match get_latest_git_tag(&repo, prefix.as_deref(), option.as_deref()) {
-        Ok(tag) => tag,
+/// The `list --json` entry of a tag, with the parsed version and where the tag points
+fn tag_json(repo: &Repository, filter: &TagFilter, tag: &str) -> serde_json::Value {
+    let details = match tag_details(repo, tag) {
+        Ok(details) => details,
         Err(e) => {
-            eprintln!("Error fetching latest tag: {}", e);
+            log_error!("Error reading tag '{}': {}", tag, e);
             process::exit(1);
         }
     };
+    let version = Version::parse(filter.version_name(tag).unwrap_or(tag)).ok();
 
-    match Version::parse(&current_version) {
-        Ok(version) => {
-            let new_version = match version.increment(scope.as_deref(), option.as_deref()) {
-                Ok(new_version) => new_version,
-                Err(e) => {
-                    eprintln!("Error incrementing version: {}", e);
-                    process::exit(1);
+    serde_json::json!({
+        "name": tag,
+        "version": version,
+        "commit": details.commit.to_string(),
+        "annotated": details.annotated,
+        "tagger_date": details.tagger_time.map(timestamp::format_rfc3339),
+    })
+}
+
+fn list(args: ListArgs, global: &GlobalArgs) {
+    let verbose = global.verbose;
+    let repo = open_repo(global);
+    let config = load_config(&repo);
+    let filter = TagFilter {
+        ref_format: tag_ref_format(args.namespace, args.tag_ref_format),
+        prefix: args
+            .prefix
+            .or(config.prefix.filter(|_| args.pattern.is_none())),
+        pattern: args.pattern,
+        include: args.include,
+        exclude: args.exclude,
+        sort: args.tag_sort,
+        cache_file: args.cache_file.map(|path| resolve_path(global, &path)),
+        ..TagFilter::default()
+    };
+
+    if args.list_remote {
+        let tags = match remote_only_tags(&repo, &filter, &args.remote, args.retries, verbose) {
+            Ok(tags) => tags,
+            Err(e) => {
+                log_error!("Error listing the tags of '{}': {}", args.remote, e);
+                process::exit(1);
+            }
+        };
+        let mut versions: Vec<(Version, String)> = tags
+            .into_iter()
+            .filter_map(|tag| {
+                let version = Version::parse(filter.version_name(&tag).unwrap_or(&tag)).ok()?;
+                Some((version, tag))
+            })
+            .collect();
+        versions.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let skip = args
+            .recent
+            .map_or(0, |recent| versions.len().saturating_sub(recent));
+        for (_, tag) in versions.into_iter().skip(skip) {
+            println!("{}", tag);
+        }
+        return;
+    }
+
+    match get_version_tags(&repo, &filter, verbose) {
+        Ok(tags) => {
+            let skip = args
+                .recent
+                .map_or(0, |recent| tags.len().saturating_sub(recent));
+            let tags = tags.into_iter().skip(skip);
+            if let Some(format) = &args.format {
+                for tag in tags {
+                    println!("{}", tag_line(&repo, &filter, &tag, format));
                 }
-            };
+                return;
+            }
+            if args.json {
+                let tags: Vec<serde_json::Value> =
+                    tags.map(|tag| tag_json(&repo, &filter, &tag)).collect();
+                println!("{}", serde_json::Value::Array(tags));
+                return;
+            }
+            for tag in tags {
+                println!("{}", tag);
+            }
+        }
+        Err(e) => {
+            log_error!("Error fetching tags: {}", e);
+            process::exit(1);
+        }
+    }
+}
 
-            let new_version_str = new_version.to_string();
+fn delete(args: DeleteArgs, global: &GlobalArgs) {
+    let repo = open_repo(global);
 
-            if dry_run {
-                println!("Latest version: '{}'", current_version);
-                println!("New version   : '{}'", new_version_str);
-            } else if let Err(e) = create_git_tag(&repo, &new_version_str) {
-                eprintln!("Error creating tag: {}", e);
+    if args.dry_run {
+        println!("Would delete tag '{}'", args.tag);
+    } else if let Err(e) = delete_git_tag(&repo, &args.tag) {
+        log_error!("Error deleting tag: {}", e);
+        process::exit(1);
+    }
+}
+
+fn diff(args: DiffArgs, global: &GlobalArgs) {
+    if args.diff_only {
+        let repo = open_repo(global);
+        match diff_stats(&repo, &args.from, &args.to) {
+            Ok(stats) => println!("{} → {}: {}", args.from, args.to, stats),
+            Err(e) => {
+                log_error!("Error diffing '{}' and '{}': {}", args.from, args.to, e);
                 process::exit(1);
             }
         }
-        Err(err) => {
-            eprintln!("Error: {}", err);
+        return;
+    }
+
+    let parse = |version: &str| match Version::parse(version) {
+        Ok(version) => version,
+        Err(e) => {
+            log_error!("Error parsing '{}': {}", version, e);
             process::exit(1);
         }
+    };
+    let from = parse(&args.from);
+    let to = parse(&args.to);
+
+    if args.distance {
+        println!("{} → {}: {}", from, to, from.distance(&to));
+    } else {
+        println!("{} → {}: {}", from, to, from.describe_change(&to));
+    }
+}
+
+/// Exits with 0 when the versions are equal, 1 when `a` is lower and 2 when `a` is higher, so
+/// that parse errors (3) are never mistaken for an ordering
+fn compare(args: CompareArgs) {
+    let parse = |version: &str| match Version::parse(version) {
+        Ok(version) => version,
+        Err(e) => {
+            log_error!("Error parsing '{}': {}", version, e);
+            process::exit(3);
+        }
+    };
+    let a = parse(&args.a);
+    let b = parse(&args.b);
+
+    let (output, code) = match a.cmp(&b) {
+        Ordering::Less => (-1, 1),
+        Ordering::Equal => (0, 0),
+        Ordering::Greater => (1, 2),
+    };
+    println!("{}", output);
+    process::exit(code);
+}
+
+fn main() {
+    // The matches are kept for --config-show, which reports where each setting came from
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    logging::init();
+    let global = &cli.global;
+
+    match cli.command {
+        Some(Command::Bump(args)) => {
+            let bump_matches = matches.subcommand_matches("bump").unwrap_or(&matches);
+            bump(*args, global, bump_matches)
+        }
+        Some(Command::Get(args)) => get(args, global),
+        Some(Command::List(args)) => list(args, global),
+        Some(Command::Delete(args)) => delete(args, global),
+        Some(Command::Diff(args)) => diff(args, global),
+        Some(Command::Compare(args)) => compare(args),
+        None => {
+            log_warn!(
+                "Warning: running semtag without a subcommand is deprecated, use `semtag bump` instead"
+            );
+            bump(cli.bump, global, &matches)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-06-15T10:15:00Z
+    const NOW: i64 = 1_718_446_500;
+
+    #[test]
+    fn build_timestamp_uses_the_given_clock() {
+        assert_eq!(
+            build_metadata(None, Some(NOW)).as_deref(),
+            Some("20240615T101500Z")
+        );
+        assert_eq!(
+            build_metadata(Some("ci.42"), Some(NOW)).as_deref(),
+            Some("ci.42.20240615T101500Z")
+        );
+        assert_eq!(
+            build_metadata(Some("ci.42"), None).as_deref(),
+            Some("ci.42")
+        );
+        assert_eq!(build_metadata(None, None), None);
+    }
+
+    #[test]
+    fn downgrade_is_returned_to_the_caller() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let signature = git2::Signature::now("semtag", "semtag@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+            .unwrap();
+        repo.tag_lightweight("2.0.0", &repo.find_object(commit, None).unwrap(), false)
+            .unwrap();
+        let filter = TagFilter::default();
+        let check = |version: &str, allow_downgrade: bool, allow_prerelease: bool| {
+            let version = Version::parse(version).unwrap();
+            check_downgrade(
+                &repo,
+                &filter,
+                &version,
+                allow_downgrade,
+                allow_prerelease,
+                0,
+            )
+            .unwrap()
+        };
+
+        assert!(check("1.5.0", false, false).is_some_and(|message| message.contains("'2.0.0'")));
+        assert!(check("2.0.0", false, false).is_some());
+        assert_eq!(check("1.5.0", true, false), None);
+        assert_eq!(check("2.0.1", false, false), None);
+        assert!(check("2.0.0-alpha", false, false).is_some());
+        assert_eq!(check("2.0.0-alpha", false, true), None);
+        // Only a pre-release of the latest release itself is accepted
+        assert!(check("1.5.0-alpha", false, true).is_some());
     }
 }
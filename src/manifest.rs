@@ -0,0 +1,79 @@
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::{Serializer, Value};
+use std::fs;
+use std::path::Path;
+use toml_edit::{value, DocumentMut};
+
+/// Sets `[package].version` in a Cargo manifest, preserving the rest of the formatting
+pub fn update_cargo_version(path: &Path, version: &str) -> Result<(), String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+    let mut document = content
+        .parse::<DocumentMut>()
+        .map_err(|e| format!("Failed to parse '{}': {}", path.display(), e))?;
+
+    let package = document
+        .get_mut("package")
+        .and_then(|package| package.as_table_like_mut())
+        .ok_or_else(|| format!("'{}' has no [package] table", path.display()))?;
+    package.insert("version", value(version));
+
+    fs::write(path, document.to_string())
+        .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+}
+
+fn detect_indent(content: &str) -> String {
+    content
+        .lines()
+        .skip(1)
+        .map(|line| {
+            line.chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect::<String>()
+        })
+        .find(|indent| !indent.is_empty())
+        .unwrap_or_else(|| "  ".to_string())
+}
+
+/// Sets `"version"` in a `package.json`, keeping the key order and the original indentation
+pub fn update_package_json(path: &Path, version: &str) -> Result<(), String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+    let mut package: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse '{}': {}", path.display(), e))?;
+
+    let field = package
+        .get_mut("version")
+        .ok_or_else(|| format!("'{}' has no \"version\" key", path.display()))?;
+    *field = Value::String(version.to_string());
+
+    let indent = detect_indent(&content);
+    let mut output = Vec::new();
+    let mut serializer =
+        Serializer::with_formatter(&mut output, PrettyFormatter::with_indent(indent.as_bytes()));
+    package
+        .serialize(&mut serializer)
+        .map_err(|e| format!("Failed to serialize '{}': {}", path.display(), e))?;
+    if content.ends_with('\n') {
+        output.push(b'\n');
+    }
+
+    fs::write(path, output).map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+}
+
+/// Replaces the content of a plain text version file, writing to a temporary file first and
+/// renaming it so that readers never see a partially written file
+pub fn write_version_file(path: &Path, version: &str) -> Result<(), String> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("'{}' is not a file path", path.display()))?;
+    let temp_path = path.with_file_name(format!(".{}.semtag.tmp", file_name.to_string_lossy()));
+
+    fs::write(&temp_path, format!("{}\n", version))
+        .map_err(|e| format!("Failed to write '{}': {}", temp_path.display(), e))?;
+    fs::rename(&temp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to replace '{}': {}", path.display(), e)
+    })
+}
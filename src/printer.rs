@@ -0,0 +1,181 @@
+use std::env;
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal, Write};
+use std::process;
+
+#[cfg(feature = "color")]
+use owo_colors::OwoColorize;
+
+use crate::cli::{ColorChoice, Format};
+use crate::logging::log_error;
+
+/// The outcome of a bump, reported by the machine-readable formats
+pub struct BumpResult<'a> {
+    pub previous: &'a str,
+    pub tag: &'a str,
+    pub version: String,
+    pub target_sha: Option<&'a str>,
+    pub dry_run: bool,
+    /// Everything but the tag was done, see `--no-tag`
+    pub no_tag: bool,
+    /// Why a dry run would not create the tag
+    pub blocked: Option<Blocked>,
+}
+
+/// A guard that would stop the tag from being created, e.g. `tag-exists`
+pub struct Blocked {
+    pub reason: &'static str,
+    pub message: String,
+}
+
+pub fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Appends `name=value` lines to the file named by `GITHUB_OUTPUT`, or prints them to stdout
+fn write_github_output(outputs: &[(&str, &str)]) -> io::Result<()> {
+    let lines: String = outputs
+        .iter()
+        .map(|(name, value)| format!("{}={}\n", name, value))
+        .collect();
+
+    match env::var_os("GITHUB_OUTPUT").filter(|path| !path.is_empty()) {
+        Some(path) => OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(lines.as_bytes()),
+        None => io::stdout().write_all(lines.as_bytes()),
+    }
+}
+
+/// All user-facing output of a command goes through the printer, which decides on the output
+/// format and whether to use colors
+#[derive(Debug, Clone, Copy)]
+pub struct Printer {
+    format: Format,
+    #[cfg_attr(not(feature = "color"), allow(dead_code))]
+    color: bool,
+}
+
+impl Printer {
+    /// With `--color auto` colors are used on a terminal unless `NO_COLOR` is set, `--no-color`
+    /// wins over `--color`. Machine-readable formats and builds without the `color` feature never
+    /// use colors.
+    pub fn new(format: Format, color: ColorChoice, no_color: bool) -> Self {
+        let color = match (no_color, color) {
+            (true, _) | (_, ColorChoice::Never) => false,
+            (false, ColorChoice::Always) => true,
+            (false, ColorChoice::Auto) => {
+                let no_color_env = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                !no_color_env && io::stdout().is_terminal()
+            }
+        };
+        let color = cfg!(feature = "color") && format == Format::Text && color;
+
+        Self { format, color }
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Prints a status message, to stderr for the machine-readable formats so that stdout stays
+    /// parseable
+    pub fn status(&self, message: &str) {
+        match self.format {
+            Format::Text => println!("{}", message),
+            Format::Json | Format::GithubActions => eprintln!("{}", message),
+        }
+    }
+
+    pub fn dry_run(&self, current_version: &str, new_version: &str) {
+        #[cfg(feature = "color")]
+        if self.color {
+            println!(
+                "{} {} {}",
+                current_version.dimmed(),
+                "→".dimmed(),
+                new_version.green().bold()
+            );
+            return;
+        }
+
+        println!("Latest version: '{}'", current_version);
+        println!("New version   : '{}'", new_version);
+    }
+
+    pub fn result(&self, result: &BumpResult) {
+        match self.format {
+            Format::Text => {}
+            Format::Json => {
+                let mut output = serde_json::json!({
+                    "previous": result.previous,
+                    "tag": result.tag,
+                    "version": result.version,
+                    "target_sha": result.target_sha,
+                    "target_short_sha": result.target_sha.map(short_sha),
+                    "dry_run": result.dry_run,
+                    "created": !result.dry_run && !result.no_tag,
+                });
+                if result.dry_run {
+                    let (reason, message) = match &result.blocked {
+                        Some(blocked) => (blocked.reason, Some(blocked.message.as_str())),
+                        None => ("dry-run", None),
+                    };
+                    output["would_create"] = result.blocked.is_none().into();
+                    output["reason"] = reason.into();
+                    output["message"] = message.into();
+                }
+                println!("{}", output);
+            }
+            Format::GithubActions => {
+                let created = if result.dry_run || result.no_tag {
+                    "false"
+                } else {
+                    "true"
+                };
+                let outputs = [
+                    ("previous", result.previous),
+                    ("next", result.tag),
+                    ("created", created),
+                ];
+                if let Err(e) = write_github_output(&outputs) {
+                    log_error!("Error writing the GitHub Actions outputs: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+    }
+
+    /// Prints the next version for each combination of arguments, for `--show-next`
+    pub fn next_versions(&self, current_version: &str, next: &[(String, String)]) {
+        match self.format {
+            Format::Text => {
+                println!("Latest version: '{}'", current_version);
+                let width = next.iter().map(|(arguments, _)| arguments.len()).max();
+                for (arguments, tag) in next {
+                    println!(
+                        "{:width$} : '{}'",
+                        arguments,
+                        tag,
+                        width = width.unwrap_or(0)
+                    );
+                }
+            }
+            Format::Json | Format::GithubActions => {
+                let output: serde_json::Map<String, serde_json::Value> = next
+                    .iter()
+                    .map(|(arguments, tag)| (arguments.clone(), tag.as_str().into()))
+                    .collect();
+                let output = serde_json::Value::Object(output).to_string();
+                if self.format == Format::Json {
+                    println!("{}", output);
+                } else if let Err(e) = write_github_output(&[("next_versions", &output)]) {
+                    log_error!("Error writing the GitHub Actions outputs: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use semtag::Version;
+
+use crate::git::CommitSummary;
+
+/// Expands `{tag}`, `{version}`, `{prefix}`, `{major}`, `{minor}`, `{patch}`, `{label}`, and
+/// `{previous}` placeholders for the given version
+pub fn render_template(template: &str, version: &Version, previous: &str) -> String {
+    template
+        .replace("{tag}", &version.to_string())
+        .replace("{version}", &version.without_prefix().to_string())
+        .replace("{prefix}", version.prefix.as_deref().unwrap_or(""))
+        .replace("{major}", &version.major.to_string())
+        .replace("{minor}", &version.minor.to_string())
+        .replace("{patch}", &version.patch.to_string())
+        .replace("{label}", version.label.as_deref().unwrap_or(""))
+        .replace("{previous}", previous)
+}
+
+/// The placeholders of a `--template-file`, the [`render_template`] ones and `{date}`
+const FILE_PLACEHOLDERS: [&str; 9] = [
+    "tag", "version", "prefix", "major", "minor", "patch", "label", "previous", "date",
+];
+
+/// Expands a `--template-file`, the [`render_template`] placeholders and `{date}`. Unlike inline
+/// templates, an unknown placeholder such as a misspelled `{verison}` is an error, while braces
+/// around anything that is not an identifier are kept as is.
+pub fn render_template_file(
+    template: &str,
+    version: &Version,
+    previous: &str,
+    date: &str,
+) -> Result<String, String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        let is_identifier =
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_identifier && !FILE_PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "Unknown placeholder '{{{}}}', available placeholders are: {}",
+                name,
+                FILE_PLACEHOLDERS
+                    .map(|name| format!("{{{}}}", name))
+                    .join(", ")
+            ));
+        }
+    }
+
+    Ok(render_template(template, version, previous).replace("{date}", date))
+}
+
+/// Expands the [`render_template`] placeholders, then `{scope}` and `{commits}`, the summaries of
+/// the commits one per line. The commits come last so that braces in commit messages are kept.
+pub fn render_tag_message(
+    template: &str,
+    version: &Version,
+    previous: &str,
+    scope: &str,
+    commits: &[CommitSummary],
+) -> String {
+    let commits: Vec<&str> = commits
+        .iter()
+        .map(|commit| commit.summary.as_str())
+        .collect();
+
+    render_template(template, version, previous)
+        .replace("{scope}", scope)
+        .replace("{commits}", &commits.join("\n"))
+}
+
+/// Renders a one-line summary for commit messages and PR bodies, e.g.
+/// "Bumping prod from 1.2.3 to 1.3.0 (minor)"
+pub fn render_summary(previous: &Version, next: &Version, reasons: &[&str]) -> String {
+    let mut summary = match &next.prefix {
+        Some(prefix) => format!("Bumping {} from", prefix),
+        None => "Bumping from".to_string(),
+    };
+    summary = format!(
+        "{} {} to {}",
+        summary,
+        previous.without_prefix(),
+        next.without_prefix()
+    );
+    if !reasons.is_empty() {
+        summary = format!("{} ({})", summary, reasons.join(", "));
+    }
+
+    summary
+}
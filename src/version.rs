@@ -0,0 +1,1264 @@
+use semver::{BuildMetadata, Prerelease, Version as SemverVersion};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// Serializes to a structured object, use [`as_string`] for the rendered string form
+///
+/// ```
+/// use semtag::Version;
+///
+/// let version = Version::parse("prod-1.3.0-rc.2").unwrap();
+/// let json = serde_json::to_string(&version).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"prefix":"prod","major":1,"minor":3,"patch":0,"label":"rc.2","rc_number":2,"build":null}"#
+/// );
+/// assert_eq!(serde_json::from_str::<Version>(&json).unwrap().to_string(), "prod-1.3.0-rc.2");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Version {
+    pub prefix: Option<String>,
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub label: Option<String>,
+    pub rc_number: Option<u32>,
+    pub build: Option<String>,
+    /// Written as v1.2.3, bumps keep the `v`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub leading_v: bool,
+}
+
+/// How far apart two versions are per component, see [`Version::distance`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionDistance {
+    pub major_delta: i64,
+    pub minor_delta: i64,
+    pub patch_delta: i64,
+}
+
+pub const SCOPE_MAJOR: &str = "major";
+pub const SCOPE_MINOR: &str = "minor";
+pub const SCOPE_PATCH: &str = "patch";
+/// Keeps major.minor.patch, so that only the pre-release option applies
+pub const SCOPE_NONE: &str = "none";
+/// Picks major, minor, or patch from the commits since the latest tag, see
+/// [`detect_scope_from_commits`]
+pub const SCOPE_AUTO: &str = "auto";
+
+/// Short and Conventional Commit spellings of the scopes, e.g. `feat` for minor
+pub const SCOPE_ALIASES: [(&str, &str); 8] = [
+    ("maj", SCOPE_MAJOR),
+    ("breaking", SCOPE_MAJOR),
+    ("min", SCOPE_MINOR),
+    ("feature", SCOPE_MINOR),
+    ("feat", SCOPE_MINOR),
+    ("pat", SCOPE_PATCH),
+    ("fix", SCOPE_PATCH),
+    ("bugfix", SCOPE_PATCH),
+];
+
+pub const OPT_ALPHA: &str = "alpha";
+pub const OPT_BETA: &str = "beta";
+pub const OPT_RC: &str = "rc";
+
+/// The pre-release options accepted unless a custom set is given, in ascending stage order
+pub const DEFAULT_OPTIONS: [&str; 3] = [OPT_ALPHA, OPT_BETA, OPT_RC];
+
+/// The release stages of a version in the order they progress through, a stable release is the
+/// last stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PrereleaseStage {
+    Alpha,
+    Beta,
+    Rc,
+    Stable,
+}
+
+impl std::fmt::Display for PrereleaseStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stage = match self {
+            Self::Alpha => OPT_ALPHA,
+            Self::Beta => OPT_BETA,
+            Self::Rc => OPT_RC,
+            Self::Stable => "stable",
+        };
+        write!(f, "{}", stage)
+    }
+}
+
+/// The errors of the library API that callers may want to tell apart
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SemtagError {
+    /// The label is not a valid semver pre-release
+    InvalidPrerelease { label: String, reason: String },
+    /// The build metadata is not valid semver build metadata
+    InvalidBuild { build: String, reason: String },
+}
+
+impl std::fmt::Display for SemtagError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPrerelease { label, reason } => {
+                write!(f, "Invalid pre-release label '{}': {}", label, reason)
+            }
+            Self::InvalidBuild { build, reason } => {
+                write!(f, "Invalid build metadata '{}': {}", build, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SemtagError {}
+
+impl Version {
+    /// Parses `[prefix-]major.minor.patch[-label][+build]`, the label keeps all of its segments
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.0.0-rc.1.hotfix").unwrap();
+    /// assert_eq!(version.label.as_deref(), Some("rc.1.hotfix"));
+    /// assert_eq!(version.rc_number, Some(1));
+    /// assert_eq!(version.to_string(), "1.0.0-rc.1.hotfix");
+    ///
+    /// let version = Version::parse("my-app-1.2.0-beta.2.fix-1+build.7").unwrap();
+    /// assert_eq!(version.prefix.as_deref(), Some("my-app"));
+    /// assert_eq!(version.label.as_deref(), Some("beta.2.fix-1"));
+    /// assert_eq!(version.build.as_deref(), Some("build.7"));
+    /// assert_eq!(version.rc_number, None);
+    ///
+    /// // A leading v is not part of the prefix and survives a bump
+    /// let version = Version::parse("prod-v1.2.3").unwrap();
+    /// assert_eq!(version.prefix.as_deref(), Some("prod"));
+    /// assert_eq!(version.bump_patch().to_string(), "prod-v1.2.4");
+    ///
+    /// // The label must be a valid semver pre-release
+    /// assert!(Version::parse("1.2.3-").is_err());
+    /// assert!(Version::parse("1.2.3-rc..1").is_err());
+    /// assert!(Version::parse("1.2.3-rc_1").is_err());
+    /// assert!(Version::parse("1.2").is_err());
+    ///
+    /// // The identifier after rc must be the counter, a bare rc counts as rc.0
+    /// assert!(Version::parse("1.2.3-rc.abc").is_err());
+    /// let version = Version::parse("1.2.3-rc").unwrap();
+    /// assert_eq!(version.rc_number, None);
+    /// assert_eq!(version.increment(None, Some("rc")).unwrap().to_string(), "1.2.3-rc.1");
+    /// ```
+    pub fn parse(version: &str) -> Result<Self, String> {
+        let (version, build) = match version.split_once('+') {
+            Some((version, build)) if !build.is_empty() => (version, Some(build.to_string())),
+            Some(_) => return Err("Invalid build metadata".to_string()),
+            None => (version, None),
+        };
+        // The first segment that is a valid semver anchors the version, so prefixes and labels
+        // can both contain hyphens, e.g. my-app-1.2.0-rc-1
+        let parts: Vec<&str> = version.split('-').collect();
+        let index = parts
+            .iter()
+            .position(|part| is_semver(part))
+            .ok_or_else(|| "Invalid version format".to_string())?;
+        let prefix = Some(parts[..index].join("-")).filter(|prefix| !prefix.is_empty());
+        let label = (index + 1 < parts.len()).then(|| parts[index + 1..].join("-"));
+        if let Some(label) = &label {
+            // semver accepts an empty pre-release, but a trailing hyphen is not a label
+            if label.is_empty() {
+                return Err("Invalid pre-release label '': it must not be empty".to_string());
+            }
+            Prerelease::new(label)
+                .map_err(|e| format!("Invalid pre-release label '{}': {}", label, e))?;
+            check_rc_number(label)?;
+        }
+
+        let version_parts: Vec<&str> = strip_v(parts[index]).split('.').collect();
+        if version_parts.len() != 3 {
+            return Err("Invalid version format".to_string());
+        }
+
+        let major = version_parts[0]
+            .parse::<u32>()
+            .map_err(|_| "Invalid major version".to_string())?;
+        let minor = version_parts[1]
+            .parse::<u32>()
+            .map_err(|_| "Invalid minor version".to_string())?;
+        let patch = version_parts[2]
+            .parse::<u32>()
+            .map_err(|_| "Invalid patch version".to_string())?;
+
+        let rc_number = label.as_deref().and_then(rc_number_of);
+
+        Ok(Self {
+            prefix,
+            major,
+            minor,
+            patch,
+            label,
+            rc_number,
+            build,
+            leading_v: parts[index].starts_with('v'),
+        })
+    }
+
+    /// Like [`Version::parse`], but also accepts the forms common in external version strings: a
+    /// missing patch, a leading `v` or `V`, a trailing fourth `.0`, and surrounding whitespace
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// assert_eq!(Version::parse_lenient("1.2").unwrap().to_string(), "1.2.0");
+    /// assert_eq!(Version::parse_lenient("V1.2.3").unwrap().to_string(), "1.2.3");
+    /// assert_eq!(Version::parse_lenient("1.0.0.0").unwrap().to_string(), "1.0.0");
+    /// assert_eq!(Version::parse_lenient(" prod-v2.1-rc.1\n").unwrap().to_string(), "prod-2.1.0-rc.1");
+    /// assert!(Version::parse_lenient("1.0.0.1").is_err());
+    /// ```
+    pub fn parse_lenient(version: &str) -> Result<Self, String> {
+        let version = version.trim();
+        let (version, build) = match version.split_once('+') {
+            Some((version, build)) => (version, Some(build)),
+            None => (version, None),
+        };
+
+        let mut parts: Vec<String> = version.split('-').map(str::to_string).collect();
+        if let Some(part) = parts.iter_mut().find(|part| normalize_core(part).is_some()) {
+            *part = normalize_core(part).unwrap_or_default();
+        }
+
+        let mut normalized = parts.join("-");
+        if let Some(build) = build {
+            normalized = format!("{}+{}", normalized, build);
+        }
+        Self::parse(&normalized)
+    }
+
+    /// The base version of a series without tags, `0.0.0` with the prefix and the pre-release
+    /// option as the label. The first release then follows from the usual bump rules:
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let initial = Version::initial(None, None);
+    /// assert_eq!(initial.to_string(), "0.0.0");
+    /// assert_eq!(initial.increment(Some("major"), None).unwrap().to_string(), "1.0.0");
+    /// assert_eq!(initial.increment(Some("minor"), None).unwrap().to_string(), "0.1.0");
+    /// assert_eq!(initial.increment(Some("patch"), None).unwrap().to_string(), "0.0.1");
+    ///
+    /// // Without a scope, the option pre-releases 0.0.0 itself
+    /// let initial = Version::initial(None, Some("alpha"));
+    /// assert_eq!(initial.to_string(), "0.0.0-alpha");
+    /// assert_eq!(initial.increment(None, Some("alpha")).unwrap().to_string(), "0.0.0-alpha");
+    /// let initial = Version::initial(None, Some("rc"));
+    /// assert_eq!(initial.increment(None, Some("rc")).unwrap().to_string(), "0.0.0-rc.1");
+    ///
+    /// // With a scope, the bump drops the label and the option starts the pre-release series
+    /// let initial = Version::initial(None, Some("beta"));
+    /// assert_eq!(initial.increment(Some("minor"), Some("beta")).unwrap().to_string(), "0.1.0-beta");
+    /// let initial = Version::initial(Some("prod"), Some("rc"));
+    /// assert_eq!(initial.increment(Some("major"), Some("rc")).unwrap().to_string(), "prod-1.0.0-rc.1");
+    ///
+    /// // There is no counter to bump on a stable base
+    /// assert!(Version::initial(None, None).bump_prerelease().is_err());
+    /// ```
+    pub fn initial(prefix: Option<&str>, option: Option<&str>) -> Self {
+        Self {
+            prefix: prefix.map(str::to_string),
+            major: 0,
+            minor: 0,
+            patch: 0,
+            label: option.map(str::to_string),
+            rc_number: option.and_then(rc_number_of),
+            build: None,
+            leading_v: false,
+        }
+    }
+
+    /// Bumps the major version and drops the pre-release label and build metadata
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("prod-1.2.3-rc.1").unwrap();
+    /// assert_eq!(version.bump_major().to_string(), "prod-2.0.0");
+    /// ```
+    pub fn bump_major(&self) -> Self {
+        Self {
+            major: self.major + 1,
+            minor: 0,
+            patch: 0,
+            build: None,
+            ..self.promote()
+        }
+    }
+
+    /// Bumps the minor version and drops the pre-release label and build metadata
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.2.3").unwrap();
+    /// assert_eq!(version.bump_minor().to_string(), "1.3.0");
+    /// ```
+    pub fn bump_minor(&self) -> Self {
+        Self {
+            minor: self.minor + 1,
+            patch: 0,
+            build: None,
+            ..self.promote()
+        }
+    }
+
+    /// Bumps the patch version and drops the pre-release label and build metadata
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.2.3-beta").unwrap();
+    /// assert_eq!(version.bump_patch().to_string(), "1.2.4");
+    /// let version = Version::parse("1.2.0+build.7").unwrap();
+    /// assert_eq!(version.bump_patch().to_string(), "1.2.1");
+    /// ```
+    pub fn bump_patch(&self) -> Self {
+        Self {
+            patch: self.patch + 1,
+            build: None,
+            ..self.promote()
+        }
+    }
+
+    /// Applies the alpha, beta, or rc pre-release option, rc increments the existing counter
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.3.0-rc.1").unwrap();
+    /// assert_eq!(version.with_prerelease("rc").unwrap().to_string(), "1.3.0-rc.2");
+    /// assert_eq!(version.with_prerelease("beta").unwrap().to_string(), "1.3.0-beta");
+    /// ```
+    pub fn with_prerelease(&self, option: &str) -> Result<Self, String> {
+        self.with_prerelease_from(option, &DEFAULT_OPTIONS)
+    }
+
+    /// Applies a pre-release option out of a custom set, e.g. dev, nightly, or snapshot. rc keeps
+    /// its counter, any other option becomes the label as is.
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.3.0").unwrap();
+    /// let stages = ["dev", "preview", "rc"];
+    /// assert_eq!(version.with_prerelease_from("preview", &stages).unwrap().to_string(), "1.3.0-preview");
+    /// assert!(version.with_prerelease_from("beta", &stages).is_err());
+    /// ```
+    pub fn with_prerelease_from(&self, option: &str, allowed: &[&str]) -> Result<Self, String> {
+        if !allowed.contains(&option) {
+            return Err(format!(
+                "Invalid option. Valid options are: {}, or just left it empty",
+                allowed.join(", ")
+            ));
+        }
+
+        // The build metadata describes the previous build, not the new pre-release
+        let mut new_version = Self {
+            build: None,
+            ..self.clone()
+        };
+        if option == OPT_RC {
+            let new_rc_number = new_version.rc_number.unwrap_or(0) + 1;
+
+            new_version.rc_number = Some(new_rc_number);
+            new_version.label = Some(format!("{}.{}", OPT_RC, new_rc_number));
+        } else {
+            Prerelease::new(option)
+                .map_err(|e| format!("Invalid pre-release option '{}': {}", option, e))?;
+
+            new_version.label = Some(option.to_string());
+            new_version.rc_number = None;
+        }
+
+        Ok(new_version)
+    }
+
+    /// Increments only the pre-release counter, the identifier right after the stage, and keeps
+    /// the core version. A label without a counter starts at 1, trailing identifiers are dropped.
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.2.0-rc.1").unwrap();
+    /// assert_eq!(version.bump_prerelease().unwrap().to_string(), "1.2.0-rc.2");
+    /// let version = Version::parse("1.2.0-beta").unwrap();
+    /// assert_eq!(version.bump_prerelease().unwrap().to_string(), "1.2.0-beta.1");
+    /// let version = Version::parse("1.2.0-alpha.3.fix").unwrap();
+    /// assert_eq!(version.bump_prerelease().unwrap().to_string(), "1.2.0-alpha.4");
+    /// assert!(Version::parse("1.2.0").unwrap().bump_prerelease().is_err());
+    /// ```
+    pub fn bump_prerelease(&self) -> Result<Self, String> {
+        let label = self
+            .prerelease()
+            .ok_or_else(|| format!("{} is not a pre-release, there is no counter to bump", self))?;
+        let mut identifiers = label.split('.');
+        let stage = identifiers.next().unwrap_or_default();
+        let counter = identifiers
+            .next()
+            .and_then(|counter| counter.parse::<u32>().ok())
+            .map_or(1, |counter| counter + 1);
+
+        let label = format!("{}.{}", stage, counter);
+        Ok(Self {
+            rc_number: rc_number_of(&label),
+            label: Some(label),
+            build: None,
+            ..self.clone()
+        })
+    }
+
+    /// Sets an explicit rc counter instead of incrementing the existing one
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.3.0").unwrap();
+    /// assert_eq!(version.with_rc_number(0).to_string(), "1.3.0-rc.0");
+    /// ```
+    pub fn with_rc_number(&self, rc_number: u32) -> Self {
+        Self {
+            label: Some(format!("{}.{}", OPT_RC, rc_number)),
+            rc_number: Some(rc_number),
+            build: None,
+            ..self.clone()
+        }
+    }
+
+    /// Sets the build metadata, which does not change the precedence of the version
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.2.0").unwrap();
+    /// let stamped = version.with_build("ci.42.20240615T101500Z").unwrap();
+    /// assert_eq!(stamped.to_string(), "1.2.0+ci.42.20240615T101500Z");
+    /// assert_eq!(stamped, version);
+    /// assert_eq!(stamped.increment(Some("patch"), None).unwrap().to_string(), "1.2.1");
+    /// assert!(version.with_build("ci 42").is_err());
+    /// ```
+    pub fn with_build(&self, build: &str) -> Result<Self, String> {
+        BuildMetadata::new(build)
+            .map_err(|e| format!("Invalid build metadata '{}': {}", build, e))?;
+
+        Ok(Self {
+            build: Some(build.to_string()),
+            ..self.clone()
+        })
+    }
+
+    /// Drops the pre-release label, promoting the version to stable
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.3.0-rc.2").unwrap();
+    /// assert_eq!(version.promote().to_string(), "1.3.0");
+    /// ```
+    pub fn promote(&self) -> Self {
+        Self {
+            label: None,
+            rc_number: None,
+            ..self.clone()
+        }
+    }
+
+    /// Bumping the core version starts a fresh series, so the rc counter restarts at `rc.1`.
+    /// Without a scope and an option there is nothing to bump, which is an error rather than a
+    /// copy of the same version.
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let version = Version::parse("1.2.0").unwrap();
+    /// assert_eq!(version.increment(Some("none"), Some("alpha")).unwrap().to_string(), "1.2.0-alpha");
+    /// assert_eq!(version.increment(None, Some("alpha")).unwrap().to_string(), "1.2.0-alpha");
+    /// assert!(version.increment(Some("none"), None).is_err());
+    /// ```
+    pub fn increment(&self, scope: Option<&str>, option: Option<&str>) -> Result<Self, String> {
+        self.increment_from(scope, option, &DEFAULT_OPTIONS)
+    }
+
+    /// Like [`Version::increment`], with the pre-release option out of a custom set
+    pub fn increment_from(
+        &self,
+        scope: Option<&str>,
+        option: Option<&str>,
+        allowed: &[&str],
+    ) -> Result<Self, String> {
+        let scope = scope.filter(|scope| *scope != SCOPE_NONE);
+        if scope.is_none() && option.is_none() {
+            return Err("Nothing to bump, specify a scope or an option".to_string());
+        }
+
+        let new_version = match scope {
+            Some(SCOPE_MAJOR) => self.bump_major(),
+            Some(SCOPE_MINOR) => self.bump_minor(),
+            Some(SCOPE_PATCH) => self.bump_patch(),
+            None => self.clone(),
+            _ => {
+                return Err(
+                    "Invalid scope. Valid scopes are: major, minor, patch, and none".to_string(),
+                )
+            }
+        };
+
+        match option {
+            Some(option) => new_version.with_prerelease_from(option, allowed),
+            None => Ok(new_version),
+        }
+    }
+
+    /// Every version one bump away, keyed by the `-s`/`-o` arguments that produce it, in the
+    /// order of the scopes and then the options
+    ///
+    /// ```
+    /// use semtag::{Version, DEFAULT_OPTIONS};
+    ///
+    /// let version = Version::parse("1.2.3").unwrap();
+    /// let next: Vec<(String, String)> = version
+    ///     .next_versions(&DEFAULT_OPTIONS)
+    ///     .into_iter()
+    ///     .map(|(arguments, version)| (arguments, version.to_string()))
+    ///     .collect();
+    /// let expected = [
+    ///     ("-s major", "2.0.0"),
+    ///     ("-s major -o alpha", "2.0.0-alpha"),
+    ///     ("-s major -o beta", "2.0.0-beta"),
+    ///     ("-s major -o rc", "2.0.0-rc.1"),
+    ///     ("-s minor", "1.3.0"),
+    ///     ("-s minor -o alpha", "1.3.0-alpha"),
+    ///     ("-s minor -o beta", "1.3.0-beta"),
+    ///     ("-s minor -o rc", "1.3.0-rc.1"),
+    ///     ("-s patch", "1.2.4"),
+    ///     ("-s patch -o alpha", "1.2.4-alpha"),
+    ///     ("-s patch -o beta", "1.2.4-beta"),
+    ///     ("-s patch -o rc", "1.2.4-rc.1"),
+    ///     ("-o alpha", "1.2.3-alpha"),
+    ///     ("-o beta", "1.2.3-beta"),
+    ///     ("-o rc", "1.2.3-rc.1"),
+    /// ];
+    /// let expected: Vec<(String, String)> = expected
+    ///     .iter()
+    ///     .map(|(arguments, version)| (arguments.to_string(), version.to_string()))
+    ///     .collect();
+    /// assert_eq!(next, expected);
+    /// ```
+    pub fn next_versions(&self, allowed: &[&str]) -> Vec<(String, Self)> {
+        let scopes = [
+            Some(SCOPE_MAJOR),
+            Some(SCOPE_MINOR),
+            Some(SCOPE_PATCH),
+            None,
+        ];
+        let options: Vec<Option<&str>> = std::iter::once(None)
+            .chain(allowed.iter().copied().map(Some))
+            .collect();
+
+        let mut next = Vec::new();
+        for scope in scopes {
+            for option in &options {
+                let arguments: Vec<String> = scope
+                    .map(|scope| format!("-s {}", scope))
+                    .into_iter()
+                    .chain(option.map(|option| format!("-o {}", option)))
+                    .collect();
+                if let Ok(version) = self.increment_from(scope, *option, allowed) {
+                    next.push((arguments.join(" "), version));
+                }
+            }
+        }
+
+        next
+    }
+
+    /// The stage of the version, `None` for labels outside of alpha, beta, and rc
+    pub fn stage(&self) -> Option<PrereleaseStage> {
+        let Some(label) = &self.label else {
+            return Some(PrereleaseStage::Stable);
+        };
+        match label.split('.').next() {
+            Some(OPT_ALPHA) => Some(PrereleaseStage::Alpha),
+            Some(OPT_BETA) => Some(PrereleaseStage::Beta),
+            Some(OPT_RC) => Some(PrereleaseStage::Rc),
+            _ => None,
+        }
+    }
+
+    /// Checks that `next` does not go back a stage within the same version, e.g. from beta to
+    /// alpha. Skipping stages is allowed, and a new version starts over at any stage.
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let beta = Version::parse("1.0.0-beta").unwrap();
+    /// assert!(beta.check_stage_order(&Version::parse("1.0.0-alpha").unwrap()).is_err());
+    /// assert!(beta.check_stage_order(&Version::parse("1.0.0-rc.1").unwrap()).is_ok());
+    /// assert!(beta.check_stage_order(&Version::parse("1.1.0-alpha").unwrap()).is_ok());
+    /// let stable = Version::parse("1.0.0").unwrap();
+    /// assert!(stable.check_stage_order(&Version::parse("1.0.0-rc.1").unwrap()).is_err());
+    /// ```
+    pub fn check_stage_order(&self, next: &Version) -> Result<(), String> {
+        let same_core =
+            (self.major, self.minor, self.patch) == (next.major, next.minor, next.patch);
+        match (self.stage(), next.stage()) {
+            (Some(current), Some(requested)) if same_core && requested < current => Err(format!(
+                "{} would go back from {} to {}, pre-releases progress from alpha to beta to rc to stable",
+                self, current, requested
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Orders like [`Ord`], except that pre-releases of the same core version are ordered by the
+    /// position of their first label identifier in `stages`, e.g. dev < preview < rc. Labels
+    /// missing from `stages` all rank below the listed ones, so that the order stays transitive.
+    ///
+    /// ```
+    /// use semtag::Version;
+    /// use std::cmp::Ordering;
+    ///
+    /// let stages = ["dev", "preview", "rc"];
+    /// let dev = Version::parse("1.3.0-dev").unwrap();
+    /// let preview = Version::parse("1.3.0-preview").unwrap();
+    /// let rc = Version::parse("1.3.0-rc.1").unwrap();
+    /// assert_eq!(preview.cmp_by_stages(&rc, &stages), Ordering::Less);
+    /// assert_eq!(dev.cmp_by_stages(&preview, &stages), Ordering::Less);
+    /// assert_eq!(preview.cmp(&dev), Ordering::Greater);
+    /// let nightly = Version::parse("1.3.0-nightly").unwrap();
+    /// assert_eq!(nightly.cmp_by_stages(&dev, &stages), Ordering::Less);
+    /// ```
+    pub fn cmp_by_stages(&self, other: &Self, stages: &[&str]) -> Ordering {
+        let stage = |version: &Self| {
+            let label = version.label.as_deref()?;
+            let identifier = label.split('.').next().unwrap_or(label);
+            stages.iter().position(|stage| *stage == identifier)
+        };
+
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| self.label.is_none().cmp(&other.label.is_none()))
+            .then_with(|| stage(self).cmp(&stage(other)))
+            .then_with(|| self.cmp(other))
+    }
+
+    /// Returns the same version without the prefix, e.g. prod-1.2.3 → 1.2.3
+    pub fn without_prefix(&self) -> Self {
+        Self {
+            prefix: None,
+            ..self.clone()
+        }
+    }
+
+    /// Whether the version has a pre-release label, e.g. 1.3.0-rc.1
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// assert!(Version::parse("prod-1.3.0-rc.1").unwrap().is_prerelease());
+    /// assert!(Version::parse("1.3.0-alpha").unwrap().is_prerelease());
+    /// assert!(!Version::parse("1.3.0").unwrap().is_prerelease());
+    /// assert!(!Version::parse("1.3.0+build.5").unwrap().is_prerelease());
+    /// ```
+    pub fn is_prerelease(&self) -> bool {
+        self.label.is_some()
+    }
+
+    /// Returns the same version without the pre-release label and build metadata, keeping the
+    /// prefix, e.g. prod-1.3.0-rc.1+build.5 → prod-1.3.0
+    ///
+    /// ```
+    /// use semtag::Version;
+    ///
+    /// let rc = Version::parse("prod-1.3.0-rc.1+build.5").unwrap();
+    /// assert_eq!(rc.core().to_string(), "prod-1.3.0");
+    /// assert!(!rc.core().is_prerelease());
+    ///
+    /// let stable = Version::parse("1.3.0").unwrap();
+    /// assert_eq!(stable.core(), stable);
+    /// ```
+    pub fn core(&self) -> Self {
+        Self {
+            label: None,
+            rc_number: None,
+            build: None,
+            ..self.clone()
+        }
+    }
+
+    /// Converts the version into a `semver::Version`, dropping the prefix
+    ///
+    /// ```
+    /// use semtag::{SemtagError, Version};
+    ///
+    /// let version = Version::parse("prod-1.3.0-rc.2+ci.7").unwrap();
+    /// assert_eq!(version.to_semver().unwrap(), semver::Version::parse("1.3.0-rc.2+ci.7").unwrap());
+    ///
+    /// let invalid = Version {
+    ///     label: Some("rc..1".to_string()),
+    ///     ..version
+    /// };
+    /// assert!(matches!(invalid.to_semver(), Err(SemtagError::InvalidPrerelease { .. })));
+    /// ```
+    pub fn to_semver(&self) -> Result<SemverVersion, SemtagError> {
+        let pre = match self.prerelease() {
+            Some(label) => Prerelease::new(&label).map_err(|e| SemtagError::InvalidPrerelease {
+                reason: e.to_string(),
+                label,
+            })?,
+            None => Prerelease::EMPTY,
+        };
+        let build = match &self.build {
+            Some(build) => BuildMetadata::new(build).map_err(|e| SemtagError::InvalidBuild {
+                build: build.clone(),
+                reason: e.to_string(),
+            })?,
+            None => BuildMetadata::EMPTY,
+        };
+
+        Ok(SemverVersion {
+            major: self.major.into(),
+            minor: self.minor.into(),
+            patch: self.patch.into(),
+            pre,
+            build,
+        })
+    }
+
+    /// Builds a version from a `semver::Version`, components above `u32::MAX` saturate
+    pub fn from_semver(version: &SemverVersion, prefix: Option<String>) -> Self {
+        let (label, rc_number) = if version.pre.is_empty() {
+            (None, None)
+        } else {
+            let pre = version.pre.as_str();
+            (Some(pre.to_string()), rc_number_of(pre))
+        };
+
+        Self {
+            prefix,
+            major: u32::try_from(version.major).unwrap_or(u32::MAX),
+            minor: u32::try_from(version.minor).unwrap_or(u32::MAX),
+            patch: u32::try_from(version.patch).unwrap_or(u32::MAX),
+            label,
+            rc_number,
+            build: (!version.build.is_empty()).then(|| version.build.to_string()),
+            leading_v: false,
+        }
+    }
+
+    /// The per-component difference from this version to `to`, negative when a component went
+    /// down. Pre-release labels, prefixes and build metadata are not part of the distance.
+    ///
+    /// ```
+    /// use semtag::{Version, VersionDistance};
+    ///
+    /// let from = Version::parse("1.4.2").unwrap();
+    /// let to = Version::parse("2.0.0-rc.1").unwrap();
+    /// let distance = from.distance(&to);
+    /// assert_eq!(
+    ///     distance,
+    ///     VersionDistance { major_delta: 1, minor_delta: -4, patch_delta: -2 }
+    /// );
+    /// assert_eq!(distance.to_string(), "major +1, minor -4, patch -2");
+    /// ```
+    pub fn distance(&self, to: &Version) -> VersionDistance {
+        let delta = |from: u32, to: u32| i64::from(to) - i64::from(from);
+
+        VersionDistance {
+            major_delta: delta(self.major, to.major),
+            minor_delta: delta(self.minor, to.minor),
+            patch_delta: delta(self.patch, to.patch),
+        }
+    }
+
+    /// Describes the change from this version to `to`, e.g. "minor bump (1 minor increment)"
+    pub fn describe_change(&self, to: &Version) -> String {
+        let deltas = [
+            (SCOPE_MAJOR, self.major, to.major),
+            (SCOPE_MINOR, self.minor, to.minor),
+            (SCOPE_PATCH, self.patch, to.patch),
+        ];
+
+        for (scope, from, to_value) in deltas {
+            if from == to_value {
+                continue;
+            }
+
+            let (kind, step, delta) = if to_value > from {
+                ("bump", "increment", to_value - from)
+            } else {
+                ("downgrade", "decrement", from - to_value)
+            };
+            let plural = if delta == 1 { "" } else { "s" };
+            let mut description = format!(
+                "{} {} ({} {} {}{})",
+                scope, kind, delta, scope, step, plural
+            );
+            if let Some(label) = to.prerelease() {
+                description = format!("{}, pre-release {}", description, label);
+            }
+            return description;
+        }
+
+        match (self.prerelease(), to.prerelease()) {
+            (Some(_), None) => "promoted to stable".to_string(),
+            (None, Some(label)) => format!("demoted to pre-release {}", label),
+            (Some(from), Some(to)) if from != to => {
+                format!("pre-release changed from {} to {}", from, to)
+            }
+            _ => "no change".to_string(),
+        }
+    }
+
+    fn precedence_key(&self) -> Result<SemverVersion, SemtagError> {
+        Self {
+            build: None,
+            ..self.clone()
+        }
+        .to_semver()
+    }
+
+    fn prerelease(&self) -> Option<String> {
+        self.label.as_ref().map(|label| {
+            if let (OPT_RC, Some(rc_number)) = (label.as_str(), self.rc_number) {
+                format!("{}.{}", OPT_RC, rc_number)
+            } else {
+                label.to_string()
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let v = if self.leading_v { "v" } else { "" };
+        let version = format!("{}{}.{}.{}", v, self.major, self.minor, self.patch);
+        let label = if let Some(label) = self.prerelease() {
+            format!("-{}", label)
+        } else {
+            String::new()
+        };
+        let prefix = if let Some(prefix) = &self.prefix {
+            format!("{}-", prefix)
+        } else {
+            String::new()
+        };
+        let build = if let Some(build) = &self.build {
+            format!("+{}", build)
+        } else {
+            String::new()
+        };
+
+        write!(f, "{}{}{}{}", prefix, version, label, build)
+    }
+}
+
+impl std::fmt::Display for VersionDistance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:+}, {} {:+}, {} {:+}",
+            SCOPE_MAJOR,
+            self.major_delta,
+            SCOPE_MINOR,
+            self.minor_delta,
+            SCOPE_PATCH,
+            self.patch_delta
+        )
+    }
+}
+
+/// Delegates to [`Version::parse`], so that `v.to_string().parse::<Version>()` yields `v` back
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        Self::parse(version)
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = String;
+
+    fn try_from(version: &str) -> Result<Self, Self::Error> {
+        Self::parse(version)
+    }
+}
+
+impl TryFrom<String> for Version {
+    type Error = String;
+
+    fn try_from(version: String) -> Result<Self, Self::Error> {
+        Self::parse(&version)
+    }
+}
+
+/// Orders by semver precedence, ignoring build metadata, with the prefix as the tiebreaker
+///
+/// ```
+/// use semtag::Version;
+/// use std::cmp::Ordering;
+///
+/// let build_7 = Version::parse("1.2.0+build.7").unwrap();
+/// let build_1 = Version::parse("1.2.0+build.1").unwrap();
+/// assert_eq!(build_7.cmp(&build_1), Ordering::Equal);
+/// assert!(build_7 < Version::parse("1.2.1").unwrap());
+/// ```
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let precedence = match (self.precedence_key(), other.precedence_key()) {
+            (Ok(a), Ok(b)) => a.cmp_precedence(&b),
+            _ => (self.major, self.minor, self.patch)
+                .cmp(&(other.major, other.minor, other.patch))
+                .then_with(|| self.label.is_none().cmp(&other.label.is_none()))
+                .then_with(|| self.prerelease().cmp(&other.prerelease())),
+        };
+
+        precedence.then_with(|| self.prefix.cmp(&other.prefix))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+/// Serializes a version as its rendered string, for use with `#[serde(with = "semtag::as_string")]`
+///
+/// ```
+/// use semtag::Version;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Release {
+///     #[serde(with = "semtag::as_string")]
+///     version: Version,
+/// }
+///
+/// let release = Release { version: Version::parse("v1.2.3+build.5").unwrap() };
+/// let json = serde_json::to_string(&release).unwrap();
+/// assert_eq!(json, r#"{"version":"v1.2.3+build.5"}"#);
+/// assert_eq!(serde_json::from_str::<Release>(&json).unwrap().version.to_string(), "v1.2.3+build.5");
+/// ```
+pub mod as_string {
+    use super::Version;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(version: &Version, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(version)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Version, D::Error> {
+        let version = String::deserialize(deserializer)?;
+        Version::parse(&version).map_err(D::Error::custom)
+    }
+}
+
+/// Picks the scope from Conventional Commit messages: major for a breaking change, marked with
+/// `!` before the colon or a `BREAKING CHANGE:` footer, minor for a `feat` commit, and patch
+/// otherwise
+///
+/// ```
+/// use semtag::detect_scope_from_commits;
+///
+/// assert_eq!(detect_scope_from_commits(["fix: typo", "feat(cli): add --auto"]), "minor");
+/// assert_eq!(detect_scope_from_commits(["feat!: drop the v1 API"]), "major");
+/// assert_eq!(detect_scope_from_commits(["refactor(git)!: rename the tag helpers"]), "major");
+/// assert_eq!(
+///     detect_scope_from_commits(["fix: use the new config\n\nBREAKING CHANGE: .semtagrc is ignored"]),
+///     "major"
+/// );
+/// assert_eq!(detect_scope_from_commits(["chore: bump deps", "docs: fix typo"]), "patch");
+/// assert_eq!(detect_scope_from_commits([]), "patch");
+/// ```
+pub fn detect_scope_from_commits<'a>(messages: impl IntoIterator<Item = &'a str>) -> &'static str {
+    let mut scope = SCOPE_PATCH;
+    for message in messages {
+        let header = message.lines().next().unwrap_or_default();
+        let Some((kind, _)) = header.split_once(':') else {
+            continue;
+        };
+        let breaking = kind.ends_with('!')
+            || message.lines().any(|line| {
+                line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+            });
+        if breaking {
+            return SCOPE_MAJOR;
+        }
+        let kind = kind.split('(').next().unwrap_or(kind).trim();
+        if kind == "feat" {
+            scope = SCOPE_MINOR;
+        }
+    }
+
+    scope
+}
+
+/// Resolves a scope or one of its [`SCOPE_ALIASES`] to the canonical scope
+///
+/// ```
+/// use semtag::normalize_scope;
+///
+/// assert_eq!(normalize_scope("breaking"), Some("major"));
+/// assert_eq!(normalize_scope("maj"), Some("major"));
+/// assert_eq!(normalize_scope("feature"), Some("minor"));
+/// assert_eq!(normalize_scope("feat"), Some("minor"));
+/// assert_eq!(normalize_scope("min"), Some("minor"));
+/// assert_eq!(normalize_scope("fix"), Some("patch"));
+/// assert_eq!(normalize_scope("bugfix"), Some("patch"));
+/// assert_eq!(normalize_scope("pat"), Some("patch"));
+/// assert_eq!(normalize_scope("patch"), Some("patch"));
+/// assert_eq!(normalize_scope("none"), Some("none"));
+/// assert_eq!(normalize_scope("chore"), None);
+/// ```
+pub fn normalize_scope(scope: &str) -> Option<&'static str> {
+    [SCOPE_MAJOR, SCOPE_MINOR, SCOPE_PATCH, SCOPE_NONE]
+        .into_iter()
+        .find(|canonical| *canonical == scope)
+        .or_else(|| {
+            SCOPE_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == scope)
+                .map(|(_, canonical)| *canonical)
+        })
+}
+
+/// Checks whether the string is a semver version, a leading `v` (v1.2.3) is accepted
+pub fn is_semver(version_str: &str) -> bool {
+    SemverVersion::parse(strip_v(version_str)).is_ok()
+}
+
+/// Normalizes `v1.2`, `V1.2.3`, and `1.2.3.0` to `1.2.3`, anything else yields `None`
+fn normalize_core(segment: &str) -> Option<String> {
+    let core = segment.strip_prefix(['v', 'V']).unwrap_or(segment);
+    let numbers: Vec<&str> = core.split('.').collect();
+    if !numbers
+        .iter()
+        .all(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+
+    match numbers.as_slice() {
+        [major, minor] => Some(format!("{}.{}.0", major, minor)),
+        [major, minor, patch] | [major, minor, patch, "0"] => {
+            Some(format!("{}.{}.{}", major, minor, patch))
+        }
+        _ => None,
+    }
+}
+
+/// The rc counter is the identifier right after `rc`, e.g. 1 for `rc.1` and `rc.1.hotfix`
+fn rc_number_of(label: &str) -> Option<u32> {
+    let mut identifiers = label.split('.');
+    if identifiers.next() != Some(OPT_RC) {
+        return None;
+    }
+
+    identifiers.next()?.parse::<u32>().ok()
+}
+
+/// Rejects an rc label whose counter is not a number, e.g. `rc.abc`, which would otherwise
+/// restart the series at rc.1
+fn check_rc_number(label: &str) -> Result<(), String> {
+    let mut identifiers = label.split('.');
+    if identifiers.next() != Some(OPT_RC) {
+        return Ok(());
+    }
+
+    match identifiers.next() {
+        Some(counter) if counter.parse::<u32>().is_err() => Err(format!(
+            "Invalid rc number '{}' in '{}', expected e.g. rc.1",
+            counter, label
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn strip_v(version_str: &str) -> &str {
+    version_str.strip_prefix('v').unwrap_or(version_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(versions: &[&str]) -> Vec<Version> {
+        versions
+            .iter()
+            .map(|version| Version::parse(version).unwrap())
+            .collect()
+    }
+
+    fn assert_ascending(list: &[&str]) {
+        let parsed = versions(list);
+        for pair in parsed.windows(2) {
+            assert_eq!(
+                pair[0].cmp(&pair[1]),
+                Ordering::Less,
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn core_versions_compare_numerically() {
+        assert_ascending(&["1.0.0", "2.0.0", "2.1.0", "2.1.1", "2.10.0", "10.0.0"]);
+    }
+
+    #[test]
+    fn prerelease_has_lower_precedence_than_release() {
+        assert_ascending(&["1.0.0-rc.1", "1.0.0", "1.0.1-alpha"]);
+    }
+
+    #[test]
+    fn prerelease_precedence_follows_the_specification_example() {
+        assert_ascending(&[
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ]);
+    }
+
+    #[test]
+    fn numeric_identifiers_sort_before_alphanumeric_ones() {
+        assert_ascending(&["1.0.0-1", "1.0.0-2", "1.0.0-10", "1.0.0-1a", "1.0.0-a"]);
+        assert_ascending(&["1.0.0-rc.9", "1.0.0-rc.10", "1.0.0-rc.10.a"]);
+    }
+
+    #[test]
+    fn build_metadata_is_ignored() {
+        let parsed = versions(&["1.0.0+build.1", "1.0.0+build.2", "1.0.0", "1.0.0+zzz"]);
+        for version in &parsed {
+            assert_eq!(version.cmp(&parsed[0]), Ordering::Equal);
+        }
+        assert_ascending(&["1.0.0-rc.1+build.9", "1.0.0+build.1"]);
+    }
+
+    fn incremented(version: &str, scope: Option<&str>, option: Option<&str>) -> String {
+        Version::parse(version)
+            .unwrap()
+            .increment(scope, option)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn rc_counter_advances_within_a_version() {
+        assert_eq!(incremented("1.3.0-rc.1", None, Some("rc")), "1.3.0-rc.2");
+        assert_eq!(incremented("1.3.0-rc.9", None, Some("rc")), "1.3.0-rc.10");
+        assert_eq!(
+            incremented("prod-1.3.0-rc.2", None, Some("rc")),
+            "prod-1.3.0-rc.3"
+        );
+        assert_eq!(incremented("1.3.0-rc", None, Some("rc")), "1.3.0-rc.1");
+        assert_eq!(incremented("1.3.0-beta", None, Some("rc")), "1.3.0-rc.1");
+    }
+
+    #[test]
+    fn rc_counter_restarts_on_a_final_release() {
+        assert_eq!(
+            incremented("1.2.0", Some("minor"), Some("rc")),
+            "1.3.0-rc.1"
+        );
+        assert_eq!(
+            incremented("1.2.0", Some("patch"), Some("rc")),
+            "1.2.1-rc.1"
+        );
+        assert_eq!(
+            incremented("1.2.0", Some("major"), Some("rc")),
+            "2.0.0-rc.1"
+        );
+        assert_eq!(incremented("1.2.0", None, Some("rc")), "1.2.0-rc.1");
+    }
+
+    #[test]
+    fn rc_counter_restarts_when_the_core_version_changes() {
+        assert_eq!(
+            incremented("1.2.0-rc.5", Some("minor"), Some("rc")),
+            "1.3.0-rc.1"
+        );
+        assert_eq!(
+            incremented("1.2.0-rc.5", Some("patch"), Some("rc")),
+            "1.2.1-rc.1"
+        );
+        assert_eq!(
+            incremented("1.2.0-rc.5", Some("major"), Some("rc")),
+            "2.0.0-rc.1"
+        );
+    }
+
+    #[test]
+    fn rc_is_formatted_with_its_counter() {
+        let rc = Version::parse("1.3.0-rc.2").unwrap();
+        assert_eq!(rc.rc_number, Some(2));
+        assert_eq!(rc.to_string(), "1.3.0-rc.2");
+        assert_eq!(rc.promote().to_string(), "1.3.0");
+        assert_eq!(rc.with_rc_number(7).to_string(), "1.3.0-rc.7");
+        assert_eq!(rc.bump_prerelease().unwrap().to_string(), "1.3.0-rc.3");
+    }
+
+    #[test]
+    fn stages_order_is_transitive_with_unknown_labels() {
+        let stages = ["rc", "alpha"];
+        let parsed = versions(&[
+            "1.0.0-rc.1",
+            "1.0.0-alpha",
+            "1.0.0-beta",
+            "1.0.0-zeta",
+            "1.0.0",
+            "0.9.0-alpha",
+            "1.1.0-beta",
+        ]);
+        for a in &parsed {
+            for b in &parsed {
+                assert_eq!(
+                    a.cmp_by_stages(b, &stages),
+                    b.cmp_by_stages(a, &stages).reverse()
+                );
+                for c in &parsed {
+                    if a.cmp_by_stages(b, &stages).is_le() && b.cmp_by_stages(c, &stages).is_le() {
+                        assert!(a.cmp_by_stages(c, &stages).is_le(), "{} <= {}", a, c);
+                    }
+                }
+            }
+        }
+
+        let mut sorted = parsed.clone();
+        sorted.sort_by(|a, b| a.cmp_by_stages(b, &stages));
+        let sorted: Vec<String> = sorted.iter().map(Version::to_string).collect();
+        assert_eq!(
+            sorted,
+            [
+                "0.9.0-alpha",
+                "1.0.0-beta",
+                "1.0.0-zeta",
+                "1.0.0-rc.1",
+                "1.0.0-alpha",
+                "1.0.0",
+                "1.1.0-beta"
+            ]
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use glob::glob;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
+
+/// A crate of a Cargo workspace, its name doubles as the tag prefix
+#[derive(Debug)]
+pub struct Member {
+    pub name: String,
+    pub manifest: PathBuf,
+}
+
+fn read_manifest(path: &Path) -> Result<DocumentMut, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+    content
+        .parse::<DocumentMut>()
+        .map_err(|e| format!("Failed to parse '{}': {}", path.display(), e))
+}
+
+fn package_name(document: &DocumentMut) -> Option<String> {
+    document
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// The string entries of a `[workspace]` array such as `members` or `exclude`
+fn workspace_paths(document: &DocumentMut, key: &str) -> Vec<String> {
+    document
+        .get("workspace")
+        .and_then(|workspace| workspace.get(key))
+        .and_then(|paths| paths.as_array())
+        .map(|paths| {
+            paths
+                .iter()
+                .filter_map(|path| path.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Lists the crates of the workspace rooted at the manifest, expanding globs such as
+/// `crates/*` and skipping `[workspace].exclude`. A root manifest that is also a package comes
+/// first.
+pub fn members(manifest: &Path) -> Result<Vec<Member>, String> {
+    let manifest = &fs::canonicalize(manifest)
+        .map_err(|e| format!("Failed to read '{}': {}", manifest.display(), e))?;
+    let document = read_manifest(manifest)?;
+    if document.get("workspace").is_none() {
+        return Err(format!("'{}' has no [workspace] table", manifest.display()));
+    }
+    let root = manifest.parent().unwrap_or_else(|| Path::new(""));
+    let excluded: Vec<PathBuf> = workspace_paths(&document, "exclude")
+        .iter()
+        .map(|path| root.join(path))
+        .collect();
+
+    let mut members = Vec::new();
+    if let Some(name) = package_name(&document) {
+        members.push(Member {
+            name,
+            manifest: manifest.to_path_buf(),
+        });
+    }
+
+    for pattern in workspace_paths(&document, "members") {
+        let pattern = root.join(&pattern);
+        let paths = glob(&pattern.to_string_lossy())
+            .map_err(|e| format!("Invalid workspace member '{}': {}", pattern.display(), e))?;
+        for path in paths.flatten() {
+            let member_manifest = path.join("Cargo.toml");
+            if excluded.contains(&path) || !member_manifest.is_file() {
+                continue;
+            }
+            let name = package_name(&read_manifest(&member_manifest)?)
+                .ok_or_else(|| format!("'{}' has no package name", member_manifest.display()))?;
+            members.push(Member {
+                name,
+                manifest: member_manifest,
+            });
+        }
+    }
+
+    if members.is_empty() {
+        return Err(format!("'{}' has no workspace members", manifest.display()));
+    }
+    Ok(members)
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::{stdout, TestRepo};
+use std::fs;
+
+fn is_annotated(repo: &TestRepo, tag: &str) -> bool {
+    repo.repo
+        .find_reference(&format!("refs/tags/{}", tag))
+        .unwrap()
+        .peel_to_tag()
+        .is_ok()
+}
+
+fn bump_patch(repo: &TestRepo, args: &[&str]) -> String {
+    repo.commit("fix: a bug");
+    stdout(repo.semtag().args(["bump", "-s", "patch"]).args(args));
+    stdout(repo.semtag().arg("get"))
+}
+
+#[test]
+fn tags_are_lightweight_by_default() {
+    let repo = TestRepo::new();
+    repo.tag("0.1.0");
+
+    let tag = bump_patch(&repo, &[]);
+    assert!(!is_annotated(&repo, &tag));
+}
+
+#[test]
+fn git_config_turns_annotation_on() {
+    let repo = TestRepo::new();
+    repo.tag("0.1.0");
+    repo.repo
+        .config()
+        .unwrap()
+        .set_bool("semtag.annotate", true)
+        .unwrap();
+
+    let tag = bump_patch(&repo, &[]);
+    assert!(is_annotated(&repo, &tag));
+
+    let tag = bump_patch(&repo, &["--lightweight"]);
+    assert!(!is_annotated(&repo, &tag));
+}
+
+#[test]
+fn config_file_takes_precedence_over_git_config() {
+    let repo = TestRepo::new();
+    repo.tag("0.1.0");
+    repo.repo
+        .config()
+        .unwrap()
+        .set_bool("semtag.annotate", true)
+        .unwrap();
+    fs::write(repo.path().join(".semtag.toml"), "annotate = false\n").unwrap();
+
+    let tag = bump_patch(&repo, &[]);
+    assert!(!is_annotated(&repo, &tag));
+}
+
+#[test]
+fn command_line_takes_precedence_over_the_config_file() {
+    let repo = TestRepo::new();
+    repo.tag("0.1.0");
+
+    fs::write(repo.path().join(".semtag.toml"), "annotate = false\n").unwrap();
+    let tag = bump_patch(&repo, &["--annotate"]);
+    assert!(is_annotated(&repo, &tag));
+
+    fs::write(repo.path().join(".semtag.toml"), "annotate = true\n").unwrap();
+    let tag = bump_patch(&repo, &[]);
+    assert!(is_annotated(&repo, &tag));
+    let tag = bump_patch(&repo, &["--lightweight"]);
+    assert!(!is_annotated(&repo, &tag));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+fn repo_with_release_notes() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.tag("0.2.0");
+    repo.commit("fix(parser): handle empty input");
+    repo.commit("docs: explain the config file");
+    repo
+}
+
+#[test]
+fn tag_message_holds_the_commits_since_the_last_tag() {
+    let repo = repo_with_release_notes();
+
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--annotate-from-changelog"]),
+    );
+
+    let tag = repo
+        .repo
+        .find_reference("refs/tags/0.2.1")
+        .unwrap()
+        .peel_to_tag()
+        .expect("--annotate-from-changelog creates an annotated tag");
+    let message = tag.message().unwrap();
+    assert!(message.starts_with("## [0.2.1] - "), "{}", message);
+    assert!(
+        message.contains("- fix(parser): handle empty input"),
+        "{}",
+        message
+    );
+    assert!(
+        message.contains("- docs: explain the config file"),
+        "{}",
+        message
+    );
+    assert!(!message.contains("initial commit"), "{}", message);
+}
+
+#[test]
+fn dry_run_prints_the_would_be_message() {
+    let repo = repo_with_release_notes();
+
+    let output = stdout(repo.semtag().args([
+        "bump",
+        "-s",
+        "patch",
+        "--annotate-from-changelog",
+        "--dry-run",
+    ]));
+
+    let message = output
+        .split_once("Tag message   :\n")
+        .map(|(_, message)| message)
+        .unwrap_or_else(|| panic!("no tag message in {}", output));
+    assert!(message.starts_with("## [0.2.1] - "), "{}", output);
+    assert!(
+        message.contains("- fix(parser): handle empty input"),
+        "{}",
+        output
+    );
+    assert!(!repo.tag_exists("0.2.1"));
+}
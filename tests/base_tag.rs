@@ -0,0 +1,58 @@
+mod common;
+
+use common::{run, stdout, TestRepo};
+
+/// 1.1.5 on an older commit, 2.0.0 as the latest tag, and a hotfix commit on top
+fn hotfix_repo() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.tag("1.1.5");
+    repo.commit("feat!: the 2.0 rewrite");
+    repo.tag("2.0.0");
+    repo.commit("fix: backport a security fix");
+    repo
+}
+
+#[test]
+fn base_tag_bumps_a_patch_off_an_older_release() {
+    let repo = hotfix_repo();
+
+    let output = stdout(repo.semtag().args([
+        "bump",
+        "-s",
+        "patch",
+        "--base-tag",
+        "1.1.5",
+        "--allow-downgrade",
+    ]));
+
+    assert!(output.contains("'1.1.6'"), "{}", output);
+    assert!(repo.tag_exists("1.1.6"));
+    assert!(!repo.tag_exists("2.0.1"));
+}
+
+#[test]
+fn base_tag_still_needs_allow_downgrade_below_the_latest() {
+    let repo = hotfix_repo();
+
+    let output = run(repo
+        .semtag()
+        .args(["bump", "-s", "patch", "--base-tag", "1.1.5"]));
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'2.0.0' already exists"));
+    assert!(!repo.tag_exists("1.1.6"));
+}
+
+#[test]
+fn base_tag_must_exist() {
+    let repo = hotfix_repo();
+
+    let output = run(repo
+        .semtag()
+        .args(["bump", "-s", "patch", "--base-tag", "1.1.4"]));
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Error: base tag '1.1.4' does not exist")
+    );
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+/// Whether the build metadata is a compact UTC timestamp, e.g. 20240615T101500Z
+fn is_timestamp(build: &str) -> bool {
+    let bytes = build.as_bytes();
+    bytes.len() == 16
+        && bytes[8] == b'T'
+        && bytes[15] == b'Z'
+        && bytes[..8]
+            .iter()
+            .chain(&bytes[9..15])
+            .all(u8::is_ascii_digit)
+}
+
+fn repo() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.tag("1.2.0");
+    repo.commit("fix: a bug");
+    repo
+}
+
+fn tags(repo: &TestRepo) -> Vec<String> {
+    let tags = repo.repo.tag_names(Some("1.2.1*")).unwrap();
+    tags.iter().flatten().map(str::to_string).collect()
+}
+
+#[test]
+fn build_timestamp_is_appended_to_the_tag() {
+    let repo = repo();
+
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--build-timestamp"]),
+    );
+    let tags = tags(&repo);
+    assert_eq!(tags.len(), 1, "{:?}", tags);
+    let build = tags[0].strip_prefix("1.2.1+").unwrap();
+    assert!(is_timestamp(build), "{}", tags[0]);
+}
+
+#[test]
+fn build_and_build_timestamp_are_joined() {
+    let repo = repo();
+
+    stdout(repo.semtag().args([
+        "bump",
+        "-s",
+        "patch",
+        "--build",
+        "ci.42",
+        "--build-timestamp",
+    ]));
+    let tags = tags(&repo);
+    let build = tags[0].strip_prefix("1.2.1+ci.42.").unwrap();
+    assert!(is_timestamp(build), "{}", tags[0]);
+}
+
+#[test]
+fn build_metadata_does_not_change_the_next_bump() {
+    let repo = repo();
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--build", "ci.1"]),
+    );
+    assert!(repo.tag_exists("1.2.1+ci.1"));
+    repo.commit("fix: another bug");
+
+    for _ in 0..2 {
+        let output = stdout(repo.semtag().args([
+            "bump",
+            "-s",
+            "patch",
+            "--build-timestamp",
+            "--format",
+            "json",
+            "--dry-run",
+        ]));
+        let result: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(result["previous"], "1.2.1+ci.1");
+        assert!(
+            result["version"].as_str().unwrap().starts_with("1.2.2+"),
+            "{}",
+            result
+        );
+    }
+}
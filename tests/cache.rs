@@ -0,0 +1,50 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+#[test]
+fn cache_is_refreshed_after_a_new_tag() {
+    let repo = TestRepo::new();
+    let cache = repo.path().join("tags.json");
+    repo.tag("1.0.0");
+
+    let get = |repo: &TestRepo| stdout(repo.semtag().arg("get").arg("--cache-file").arg(&cache));
+    assert_eq!(get(&repo), "1.0.0");
+    assert!(cache.exists());
+
+    repo.commit("feat: a feature");
+    repo.tag("1.1.0");
+    assert_eq!(get(&repo), "1.1.0");
+}
+
+#[test]
+fn cache_is_refreshed_after_a_new_namespaced_tag() {
+    let repo = TestRepo::new();
+    let cache = repo.path().join("tags.json");
+    repo.tag("releases/1.0.0");
+
+    let get = |repo: &TestRepo| {
+        stdout(
+            repo.semtag()
+                .args(["get", "--namespace", "releases", "--cache-file"])
+                .arg(&cache),
+        )
+    };
+    assert_eq!(get(&repo), "releases/1.0.0");
+
+    repo.commit("feat: a feature");
+    repo.tag("releases/1.1.0");
+    assert_eq!(get(&repo), "releases/1.1.0");
+}
+
+#[test]
+fn cache_stores_the_tag_names() {
+    let repo = TestRepo::new();
+    let cache = repo.path().join("tags.json");
+    repo.tag("1.0.0");
+    stdout(repo.semtag().arg("get").arg("--cache-file").arg(&cache));
+
+    let content = std::fs::read_to_string(&cache).unwrap();
+    let names: Vec<String> = serde_json::from_str(&content).unwrap();
+    assert_eq!(names, ["1.0.0"]);
+}
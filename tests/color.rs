@@ -0,0 +1,86 @@
+mod common;
+
+use common::{run, TestRepo};
+use std::process::Command;
+
+const ESCAPE: char = '\u{1b}';
+
+fn repo() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.tag("1.0.0");
+    repo.commit("fix: a bug");
+    repo
+}
+
+/// Stdout and stderr of the run, which must succeed
+fn output(command: &mut Command) -> String {
+    let output = run(command);
+    assert!(output.status.success(), "{:?}", output);
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn piped_output_has_no_escape_codes() {
+    let repo = repo();
+
+    let text = output(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--dry-run"])
+            .env_remove("NO_COLOR"),
+    );
+    assert!(text.contains("1.0.1"), "{}", text);
+    assert!(!text.contains(ESCAPE), "{:?}", text);
+}
+
+#[test]
+fn no_color_disables_color() {
+    let repo = repo();
+
+    let text = output(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--dry-run"])
+            .env("NO_COLOR", "1"),
+    );
+    assert!(!text.contains(ESCAPE), "{:?}", text);
+
+    let text = output(repo.semtag().args([
+        "bump",
+        "-s",
+        "patch",
+        "--dry-run",
+        "--color",
+        "always",
+        "--no-color",
+    ]));
+    assert!(!text.contains(ESCAPE), "{:?}", text);
+}
+
+#[test]
+fn json_is_never_colored() {
+    let repo = repo();
+
+    let text = output(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--dry-run", "--format", "json"])
+            .args(["--color", "always"])
+            .env_remove("NO_COLOR"),
+    );
+    assert!(!text.contains(ESCAPE), "{:?}", text);
+}
+
+#[cfg(feature = "color")]
+#[test]
+fn forced_color_is_colored() {
+    let repo = repo();
+
+    let text = output(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--dry-run", "--color", "always"])
+            .env_remove("NO_COLOR"),
+    );
+    assert!(text.contains(ESCAPE), "{:?}", text);
+}
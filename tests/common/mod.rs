@@ -0,0 +1,155 @@
+//! A throwaway git repository and a way to run the semtag binary against it
+#![allow(dead_code)]
+
+use git2::{Oid, Repository, Signature};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+pub struct TestRepo {
+    pub dir: TempDir,
+    pub repo: Repository,
+}
+
+impl TestRepo {
+    /// A repository with an identity and one commit
+    pub fn new() -> Self {
+        let repo = Self::empty();
+        repo.commit("initial commit");
+        repo
+    }
+
+    /// A repository with an identity but no commits, HEAD is unborn
+    pub fn empty() -> Self {
+        let dir = TempDir::new().expect("create a temporary directory");
+        let repo = Repository::init(dir.path()).expect("init the repository");
+        let mut config = repo.config().expect("open the repository config");
+        config.set_str("user.name", "Semtag Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        Self { dir, repo }
+    }
+
+    /// A bare repository, e.g. to act as a remote
+    pub fn bare() -> Self {
+        let dir = TempDir::new().expect("create a temporary directory");
+        let repo = Repository::init_bare(dir.path()).expect("init the bare repository");
+
+        Self { dir, repo }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Appends the message to a file and commits it on HEAD
+    pub fn commit(&self, message: &str) -> Oid {
+        let file = self.path().join("CHANGES");
+        let mut content = fs::read_to_string(&file).unwrap_or_default();
+        content.push_str(message);
+        content.push('\n');
+        fs::write(&file, content).unwrap();
+
+        let mut index = self.repo.index().unwrap();
+        index.add_path(Path::new("CHANGES")).unwrap();
+        index.write().unwrap();
+        let tree = self.repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Semtag Test", "test@example.com").unwrap();
+        let parent = self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+
+        self.repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+    }
+
+    /// Creates a lightweight tag on HEAD
+    pub fn tag(&self, name: &str) {
+        let head = self.repo.head().unwrap().peel_to_commit().unwrap();
+        self.repo
+            .tag_lightweight(name, head.as_object(), false)
+            .unwrap();
+    }
+
+    /// Creates an annotated tag on HEAD whose tagger time is the given Unix time
+    pub fn tag_at(&self, name: &str, seconds: i64) {
+        let head = self.repo.head().unwrap().peel_to_commit().unwrap();
+        let tagger = Signature::new(
+            "Semtag Test",
+            "test@example.com",
+            &git2::Time::new(seconds, 0),
+        )
+        .unwrap();
+        self.repo
+            .tag(name, head.as_object(), &tagger, name, false)
+            .unwrap();
+    }
+
+    /// Creates an annotated tag on HEAD
+    pub fn annotated_tag(&self, name: &str, message: &str) {
+        let head = self.repo.head().unwrap().peel_to_commit().unwrap();
+        let signature = Signature::now("Semtag Test", "test@example.com").unwrap();
+        self.repo
+            .tag(name, head.as_object(), &signature, message, false)
+            .unwrap();
+    }
+
+    pub fn tag_exists(&self, name: &str) -> bool {
+        self.repo
+            .find_reference(&format!("refs/tags/{}", name))
+            .is_ok()
+    }
+
+    /// The semtag binary, run in the repository without any semtag or git settings inherited
+    /// from the environment of the test
+    pub fn semtag(&self) -> Command {
+        semtag_in(self.path())
+    }
+}
+
+/// The semtag binary, run in `dir` without any semtag or git settings inherited from the
+/// environment of the test
+pub fn semtag_in(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_semtag"));
+    command.current_dir(dir);
+    for (key, _) in env::vars_os() {
+        let inherited = key.to_string_lossy();
+        if inherited.starts_with("SEMTAG_") || inherited.starts_with("GIT_") {
+            command.env_remove(&key);
+        }
+    }
+    command.env_remove("RUST_LOG").env("NO_COLOR", "1");
+    command
+}
+
+/// Runs the command and returns its stdout, failing the test with stderr when it fails
+pub fn stdout(command: &mut Command) -> String {
+    let output = run(command);
+    assert!(
+        output.status.success(),
+        "semtag failed with {}: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .trim_end()
+        .to_string()
+}
+
+pub fn run(command: &mut Command) -> Output {
+    command.output().expect("run semtag")
+}
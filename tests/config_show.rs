@@ -0,0 +1,76 @@
+mod common;
+
+use common::{stdout, TestRepo};
+use std::fs;
+
+#[test]
+fn config_show_reports_the_source_of_each_setting() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join(".semtag.toml"), "annotate = true\n").unwrap();
+
+    let output = stdout(
+        repo.semtag()
+            .args(["bump", "-s", "minor", "--post-tag-command", "./notify.sh"])
+            .arg("--config-show")
+            .env("SEMTAG_PREFIX", "stage"),
+    );
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            r#"scope = "minor" # flag"#,
+            r#"post_tag_command = "./notify.sh" # flag"#,
+            r#"prefix = "stage" # environment"#,
+            "annotate = true # .semtag.toml",
+            r#"allowed_options = ["alpha", "beta", "rc"] # default"#,
+            r#"remote = "origin" # default"#,
+            "retries = 3 # default",
+            "fetch = false # default",
+            "push = false # default",
+        ]
+    );
+}
+
+#[test]
+fn config_show_reports_config_file_and_git_config() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join(".semtag.toml"), "prefix = \"prod\"\n").unwrap();
+    repo.repo
+        .config()
+        .unwrap()
+        .set_bool("semtag.annotate", true)
+        .unwrap();
+
+    let output = stdout(
+        repo.semtag()
+            .args(["bump", "--config-show", "--remote", "upstream"])
+            .env("SEMTAG_FETCH", "true"),
+    );
+    assert!(
+        output.contains("prefix = \"prod\" # .semtag.toml\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("annotate = true # git config\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("remote = \"upstream\" # flag\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("fetch = true # environment\n"),
+        "{}",
+        output
+    );
+    assert!(!output.contains("scope"), "{}", output);
+
+    let output = stdout(
+        repo.semtag()
+            .args(["bump", "--config-show", "--lightweight"]),
+    );
+    assert!(output.contains("annotate = false # flag\n"), "{}", output);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+/// The `Git command` line of the dry run
+fn git_command(output: &str) -> &str {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("Git command   : "))
+        .unwrap_or_else(|| panic!("no git command in {}", output))
+}
+
+#[test]
+fn dry_run_prints_the_signed_tag_command() {
+    let repo = TestRepo::new();
+    repo.tag("1.0.0");
+    let head = repo.commit("feat: a feature");
+
+    let output = stdout(repo.semtag().args([
+        "bump",
+        "-s",
+        "minor",
+        "--dry-run",
+        "-a",
+        "-m",
+        "Release 1.1.0",
+        "--sign",
+    ]));
+    assert_eq!(
+        git_command(&output),
+        format!("git tag -s -m 'Release 1.1.0' 1.1.0 {}", head)
+    );
+
+    let output = stdout(repo.semtag().args([
+        "bump",
+        "-s",
+        "minor",
+        "--dry-run",
+        "-m",
+        "Release",
+        "--sign=ABCD1234",
+    ]));
+    assert_eq!(
+        git_command(&output),
+        format!("git tag -u ABCD1234 -m Release 1.1.0 {}", head)
+    );
+    assert!(!repo.tag_exists("1.1.0"));
+}
+
+#[test]
+fn dry_run_prints_the_lightweight_tag_command() {
+    let repo = TestRepo::new();
+    repo.tag("prod-1.0.0");
+    let head = repo.commit("fix: a bug");
+
+    let output = stdout(
+        repo.semtag()
+            .args(["bump", "-p", "prod", "-s", "patch", "--dry-run"]),
+    );
+    assert_eq!(git_command(&output), format!("git tag prod-1.0.1 {}", head));
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use common::{run, TestRepo};
+
+const ALREADY_TAGGED: &str =
+    "HEAD is already tagged as '4.2.0', the new tag would release the same commit";
+
+#[test]
+fn bump_on_the_latest_tag_commit_warns() {
+    let repo = TestRepo::new();
+    repo.commit("feat: the 4.2 release");
+    repo.annotated_tag("4.2.0", "Release 4.2.0");
+
+    let output = run(repo.semtag().args(["bump", "-s", "minor"]));
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("Warning: {}", ALREADY_TAGGED)),
+        "{}",
+        stderr
+    );
+    assert!(repo.tag_exists("4.3.0"));
+}
+
+#[test]
+fn bump_on_the_latest_tag_commit_is_an_error_under_strict() {
+    let repo = TestRepo::new();
+    repo.commit("feat: the 4.2 release");
+    repo.annotated_tag("4.2.0", "Release 4.2.0");
+
+    let output = run(repo.semtag().args(["bump", "-s", "minor", "--strict"]));
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("Error: {}", ALREADY_TAGGED)),
+        "{}",
+        stderr
+    );
+    assert!(!repo.tag_exists("4.3.0"));
+}
+
+#[test]
+fn bump_after_a_new_commit_does_not_warn() {
+    let repo = TestRepo::new();
+    repo.annotated_tag("4.2.0", "Release 4.2.0");
+    repo.commit("fix: a bug");
+
+    let output = run(repo.semtag().args(["bump", "-s", "patch", "--strict"]));
+
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("HEAD is already tagged"));
+}
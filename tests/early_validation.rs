@@ -0,0 +1,53 @@
+mod common;
+
+use common::{run, semtag_in};
+use tempfile::TempDir;
+
+// Outside of a repository, any error about opening it would mean git was touched before the
+// arguments were checked
+fn rejected_outside_a_repository(args: &[&str]) -> String {
+    let dir = TempDir::new().unwrap();
+    let output = run(semtag_in(dir.path()).args(args));
+    assert!(!output.status.success(), "{:?} succeeded", args);
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert!(!stderr.contains("is not a git repository"), "{}", stderr);
+    stderr
+}
+
+#[test]
+fn unknown_scope_is_rejected_with_the_valid_scopes() {
+    let stderr = rejected_outside_a_repository(&["bump", "-s", "foobar"]);
+    assert!(
+        stderr.contains("invalid value 'foobar' for '--scope <SCOPE>'"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("[possible values: major, minor, patch, none, auto]"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn unknown_option_is_rejected_with_the_allowed_options() {
+    let stderr = rejected_outside_a_repository(&["bump", "-s", "minor", "-o", "gamma"]);
+    assert!(
+        stderr.contains(
+            "invalid value 'gamma' for '--option <OPTION>', allowed options are: alpha, beta, rc"
+        ),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn option_is_checked_against_the_custom_allowed_options() {
+    let stderr =
+        rejected_outside_a_repository(&["bump", "-o", "rc", "--allowed-options", "dev,preview"]);
+    assert!(
+        stderr.contains("allowed options are: dev, preview"),
+        "{}",
+        stderr
+    );
+}
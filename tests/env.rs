@@ -0,0 +1,95 @@
+mod common;
+
+use common::{run, stdout, TestRepo};
+
+fn repo() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.tag("1.0.0");
+    repo.tag("prod-1.1.0");
+    repo.tag("dev-1.2.0");
+    repo
+}
+
+#[test]
+fn environment_alone_configures_the_run() {
+    let repo = repo();
+
+    assert_eq!(
+        stdout(repo.semtag().arg("get").env("SEMTAG_PREFIX", "prod")),
+        "prod-1.1.0"
+    );
+
+    repo.commit("feat: a feature");
+    stdout(
+        repo.semtag()
+            .arg("bump")
+            .env("SEMTAG_PREFIX", "prod")
+            .env("SEMTAG_SCOPE", "minor"),
+    );
+    assert!(repo.tag_exists("prod-1.2.0"));
+}
+
+#[test]
+fn flags_take_precedence_over_the_environment() {
+    let repo = repo();
+
+    assert_eq!(
+        stdout(
+            repo.semtag()
+                .args(["get", "--prefix", "dev"])
+                .env("SEMTAG_PREFIX", "prod")
+        ),
+        "dev-1.2.0"
+    );
+
+    repo.commit("fix: a bug");
+    stdout(
+        repo.semtag()
+            .args(["bump", "--prefix", "dev", "-s", "patch"])
+            .env("SEMTAG_PREFIX", "prod")
+            .env("SEMTAG_SCOPE", "major"),
+    );
+    assert!(repo.tag_exists("dev-1.2.1"));
+    assert!(!repo.tag_exists("prod-2.0.0"));
+}
+
+#[test]
+fn boolean_flags_accept_environment_values() {
+    let repo = repo();
+    repo.commit("fix: a bug");
+
+    for value in ["true", "1", "yes", "on"] {
+        stdout(
+            repo.semtag()
+                .args(["bump", "-s", "patch"])
+                .env("SEMTAG_DRY_RUN", value),
+        );
+        assert!(!repo.tag_exists("1.0.1"), "SEMTAG_DRY_RUN={}", value);
+    }
+
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "patch"])
+            .env("SEMTAG_DRY_RUN", "false"),
+    );
+    assert!(repo.tag_exists("1.0.1"));
+}
+
+#[test]
+fn verbose_level_comes_from_the_environment() {
+    let repo = repo();
+
+    let quiet = run(repo.semtag().arg("get").env("SEMTAG_VERBOSE", "0"));
+    assert!(!String::from_utf8_lossy(&quiet.stderr).contains("[debug]"));
+
+    let verbose = run(repo.semtag().arg("get").env("SEMTAG_VERBOSE", "2"));
+    assert!(verbose.status.success());
+    let stderr = String::from_utf8_lossy(&verbose.stderr);
+    assert!(stderr.contains("[debug] Tag glob:"), "{}", stderr);
+
+    // -v on the command line replaces the level of the environment
+    let flag = run(repo.semtag().args(["get", "-v"]).env("SEMTAG_VERBOSE", "2"));
+    let stderr = String::from_utf8_lossy(&flag.stderr);
+    assert!(stderr.contains("[debug] All tags:"), "{}", stderr);
+    assert!(!stderr.contains("[debug] Tag glob:"), "{}", stderr);
+}
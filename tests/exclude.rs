@@ -0,0 +1,49 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+fn repo() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.tag("prod-1.0.0");
+    repo.tag("prod-1.1.0");
+    repo.tag("prod-9999.0.0");
+    repo
+}
+
+#[test]
+fn excluded_tag_is_not_the_latest() {
+    let repo = repo();
+
+    assert_eq!(
+        stdout(repo.semtag().args(["get", "-p", "prod"])),
+        "prod-9999.0.0"
+    );
+    assert_eq!(
+        stdout(
+            repo.semtag()
+                .args(["get", "-p", "prod", "-x", "prod-9999.*"])
+        ),
+        "prod-1.1.0"
+    );
+}
+
+#[test]
+fn excluded_tag_is_not_the_base_of_a_bump() {
+    let repo = repo();
+    repo.tag("prod-5000.0.0");
+    repo.commit("fix: a bug");
+
+    stdout(repo.semtag().args([
+        "bump",
+        "-p",
+        "prod",
+        "-s",
+        "patch",
+        "-x",
+        "prod-9999.*",
+        "--exclude",
+        "prod-5000.*",
+        "--allow-downgrade",
+    ]));
+    assert!(repo.tag_exists("prod-1.1.1"));
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::{stdout, TestRepo};
+use git2::Signature;
+
+/// Commits a breaking change on a side branch off HEAD and merges it back with a merge commit
+/// whose message carries no Conventional Commits type
+fn merge_breaking_side_branch(repo: &TestRepo) {
+    let signature = Signature::now("Semtag Test", "test@example.com").unwrap();
+    let base = repo.repo.head().unwrap().peel_to_commit().unwrap();
+    let tree = base.tree().unwrap();
+    let side = repo
+        .repo
+        .commit(
+            None,
+            &signature,
+            &signature,
+            "feat!: drop the legacy API",
+            &tree,
+            &[&base],
+        )
+        .unwrap();
+    let side = repo.repo.find_commit(side).unwrap();
+
+    repo.commit("fix: a bug on the trunk");
+    let trunk = repo.repo.head().unwrap().peel_to_commit().unwrap();
+    repo.repo
+        .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Merge branch 'legacy-removal'",
+            &trunk.tree().unwrap(),
+            &[&trunk, &side],
+        )
+        .unwrap();
+}
+
+fn next_auto_version(repo: &TestRepo, first_parent: bool) -> String {
+    let mut command = repo.semtag();
+    command.args(["bump", "-s", "auto", "--dry-run", "--format", "json"]);
+    if first_parent {
+        command.arg("--first-parent");
+    }
+    let result: serde_json::Value = serde_json::from_str(&stdout(&mut command)).unwrap();
+    result["version"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn auto_scope_sees_merged_commits_by_default() {
+    let repo = TestRepo::new();
+    repo.tag("1.4.0");
+    merge_breaking_side_branch(&repo);
+
+    assert_eq!(next_auto_version(&repo, false), "2.0.0");
+}
+
+#[test]
+fn first_parent_only_considers_the_trunk() {
+    let repo = TestRepo::new();
+    repo.tag("1.4.0");
+    merge_breaking_side_branch(&repo);
+
+    assert_eq!(next_auto_version(&repo, true), "1.4.1");
+}
+
+#[test]
+fn first_parent_leaves_merged_commits_out_of_the_changelog_entry() {
+    let repo = TestRepo::new();
+    repo.tag("1.4.0");
+    merge_breaking_side_branch(&repo);
+
+    let entry =
+        stdout(
+            repo.semtag()
+                .args(["bump", "-s", "patch", "--changelog-entry", "--first-parent"]),
+        );
+
+    assert!(entry.contains("a bug on the trunk"), "{}", entry);
+    assert!(!entry.contains("drop the legacy API"), "{}", entry);
+}
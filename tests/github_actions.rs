@@ -0,0 +1,67 @@
+mod common;
+
+use common::{stdout, TestRepo};
+use std::fs;
+use tempfile::NamedTempFile;
+
+#[test]
+fn outputs_are_appended_to_the_github_output_file() {
+    let repo = TestRepo::new();
+    repo.tag("3.4.5");
+    repo.commit("feat: a feature");
+    let output_file = NamedTempFile::new().unwrap();
+    fs::write(output_file.path(), "earlier=step\n").unwrap();
+
+    let printed = stdout(
+        repo.semtag()
+            .args(["bump", "-s", "minor", "--format", "github-actions"])
+            .env("GITHUB_OUTPUT", output_file.path()),
+    );
+
+    assert_eq!(printed, "");
+    assert_eq!(
+        fs::read_to_string(output_file.path()).unwrap(),
+        "earlier=step\nprevious=3.4.5\nnext=3.5.0\ncreated=true\n"
+    );
+}
+
+#[test]
+fn dry_run_reports_not_created() {
+    let repo = TestRepo::new();
+    repo.tag("3.4.5");
+    repo.commit("fix: a bug");
+    let output_file = NamedTempFile::new().unwrap();
+
+    stdout(
+        repo.semtag()
+            .args([
+                "bump",
+                "-s",
+                "patch",
+                "--dry-run",
+                "--format",
+                "github-actions",
+            ])
+            .env("GITHUB_OUTPUT", output_file.path()),
+    );
+
+    assert_eq!(
+        fs::read_to_string(output_file.path()).unwrap(),
+        "previous=3.4.5\nnext=3.4.6\ncreated=false\n"
+    );
+}
+
+#[test]
+fn outputs_go_to_stdout_without_github_output() {
+    let repo = TestRepo::new();
+    repo.tag("3.4.5");
+    repo.commit("fix: a bug");
+
+    let printed = stdout(
+        repo.semtag()
+            .args(["bump", "-s", "major", "--format", "github-actions"])
+            .env_remove("GITHUB_OUTPUT"),
+    );
+
+    assert_eq!(printed, "previous=3.4.5\nnext=4.0.0\ncreated=true");
+}
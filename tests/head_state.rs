@@ -0,0 +1,41 @@
+mod common;
+
+use common::{run, TestRepo};
+
+#[test]
+fn detached_head_tags_the_checked_out_commit() {
+    let repo = TestRepo::new();
+    repo.tag("1.0.0");
+    let detached = repo.commit("fix: a bug");
+    repo.commit("fix: another bug");
+    repo.repo.set_head_detached(detached).unwrap();
+
+    let output = run(repo.semtag().args(["bump", "-s", "patch"]));
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("HEAD is detached"), "{}", stderr);
+
+    let tag = repo.repo.revparse_single("1.0.1").unwrap();
+    assert_eq!(tag.peel_to_commit().unwrap().id(), detached);
+}
+
+#[test]
+fn unborn_branch_has_no_commits_to_tag() {
+    let repo = TestRepo::empty();
+
+    let output = run(repo.semtag().args(["bump", "-s", "minor"]));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no commits yet"), "{}", stderr);
+    assert!(!repo.tag_exists("0.1.0"));
+}
+
+#[test]
+fn unborn_branch_can_preview_the_first_version() {
+    let repo = TestRepo::empty();
+
+    let output = run(repo.semtag().args(["bump", "-s", "minor", "--dry-run"]));
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("0.1.0"), "{}", stdout);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{run, TestRepo};
+use std::process::Command;
+use tempfile::TempDir;
+
+/// A tagged repository whose config has no identity
+fn repo() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.tag("1.0.0");
+    repo.commit("fix: a bug");
+    let mut config = repo.repo.config().unwrap();
+    config.remove("user.name").unwrap();
+    config.remove("user.email").unwrap();
+    repo
+}
+
+/// An annotated patch bump that cannot find a global or system git config either
+fn bump(repo: &TestRepo, home: &TempDir) -> Command {
+    let mut command = repo.semtag();
+    command
+        .args(["bump", "-s", "patch", "-a"])
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .env("GIT_CONFIG_NOSYSTEM", "1");
+    command
+}
+
+fn tagger(repo: &TestRepo, tag: &str) -> (String, String) {
+    let tag = repo
+        .repo
+        .revparse_single(tag)
+        .unwrap()
+        .peel_to_tag()
+        .unwrap();
+    let tagger = tag.tagger().unwrap();
+    (
+        tagger.name().unwrap().to_string(),
+        tagger.email().unwrap().to_string(),
+    )
+}
+
+#[test]
+fn committer_environment_is_the_tagger() {
+    let repo = repo();
+    let home = TempDir::new().unwrap();
+
+    let output = run(bump(&repo, &home)
+        .env("GIT_COMMITTER_NAME", "CI Bot")
+        .env("GIT_COMMITTER_EMAIL", "ci@example.com")
+        .env("GIT_AUTHOR_NAME", "Someone Else")
+        .env("GIT_AUTHOR_EMAIL", "else@example.com"));
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        tagger(&repo, "1.0.1"),
+        ("CI Bot".to_string(), "ci@example.com".to_string())
+    );
+}
+
+#[test]
+fn author_environment_is_the_fallback() {
+    let repo = repo();
+    let home = TempDir::new().unwrap();
+
+    let output = run(bump(&repo, &home)
+        .env("GIT_AUTHOR_NAME", "Release Author")
+        .env("GIT_AUTHOR_EMAIL", "author@example.com"));
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        tagger(&repo, "1.0.1"),
+        (
+            "Release Author".to_string(),
+            "author@example.com".to_string()
+        )
+    );
+}
+
+#[test]
+fn missing_identity_is_an_error() {
+    let repo = repo();
+    let home = TempDir::new().unwrap();
+
+    let output = run(&mut bump(&repo, &home));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No identity found"), "{}", stderr);
+    assert!(!repo.tag_exists("1.0.1"));
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+fn repo() -> TestRepo {
+    let repo = TestRepo::new();
+    for tag in ["1.0.0", "1.1.0", "2.0.0", "prod-1.4.0", "prod-2.0.0"] {
+        repo.tag(tag);
+    }
+    repo
+}
+
+#[test]
+fn include_narrows_the_candidates() {
+    let repo = repo();
+
+    assert_eq!(stdout(repo.semtag().args(["get", "-i", "1.*"])), "1.1.0");
+}
+
+#[test]
+fn include_and_prefix_must_both_match() {
+    let repo = repo();
+
+    assert_eq!(
+        stdout(repo.semtag().args(["get", "-p", "prod", "-i", "*-1.*"])),
+        "prod-1.4.0"
+    );
+}
+
+#[test]
+fn exclude_takes_precedence_over_include() {
+    let repo = repo();
+
+    assert_eq!(
+        stdout(repo.semtag().args(["get", "-i", "1.*", "-x", "1.1.*"])),
+        "1.0.0"
+    );
+    let listed = stdout(
+        repo.semtag()
+            .args(["list", "-i", "1.*", "-i", "2.*", "-x", "1.1.*"]),
+    );
+    assert_eq!(listed.lines().collect::<Vec<_>>(), ["1.0.0", "2.0.0"]);
+}
+
+#[test]
+fn include_selects_the_base_of_a_bump() {
+    let repo = repo();
+    repo.commit("feat: a feature");
+
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "minor", "-i", "1.*", "--allow-downgrade"]),
+    );
+    assert!(repo.tag_exists("1.2.0"));
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::{stdout, TestRepo};
+use serde_json::Value;
+
+fn bump_json(repo: &TestRepo, args: &[&str]) -> Value {
+    let output = stdout(repo.semtag().args(["bump", "--format", "json"]).args(args));
+    serde_json::from_str(&output).unwrap_or_else(|e| panic!("{}: {}", e, output))
+}
+
+#[test]
+fn json_reports_the_full_sha_of_head() {
+    let repo = TestRepo::new();
+    repo.tag("0.9.0");
+    let head = repo.commit("feat: the last feature before 1.0").to_string();
+
+    let result = bump_json(&repo, &["-s", "major"]);
+
+    let target_sha = result["target_sha"].as_str().unwrap();
+    assert_eq!(target_sha.len(), 40);
+    assert!(target_sha.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(target_sha, head);
+    assert_eq!(result["target_short_sha"], head[..7]);
+    assert_eq!(result["tag"], "1.0.0");
+    assert_eq!(result["created"], true);
+}
+
+#[test]
+fn json_dry_run_would_create_the_tag() {
+    let repo = TestRepo::new();
+    repo.tag("0.9.0");
+    let head = repo.commit("fix: a bug").to_string();
+
+    let result = bump_json(&repo, &["-s", "patch", "--dry-run"]);
+
+    assert_eq!(result["previous"], "0.9.0");
+    assert_eq!(result["tag"], "0.9.1");
+    assert_eq!(result["target_sha"], head);
+    assert_eq!(result["dry_run"], true);
+    assert_eq!(result["created"], false);
+    assert_eq!(result["would_create"], true);
+    assert_eq!(result["reason"], "dry-run");
+    assert!(!repo.tag_exists("0.9.1"));
+}
+
+#[test]
+fn json_dry_run_reports_an_existing_tag() {
+    let repo = TestRepo::new();
+    repo.tag("0.9.0");
+    repo.commit("fix: a bug");
+    repo.tag("0.9.1");
+    repo.commit("fix: another bug");
+
+    // The guard fails a real run, the dry run reports it and still succeeds
+    let result = bump_json(&repo, &["-s", "patch", "--dry-run", "--base-tag", "0.9.0"]);
+
+    assert_eq!(result["tag"], "0.9.1");
+    assert_eq!(result["would_create"], false);
+    assert_eq!(result["reason"], "tag-exists");
+    assert!(result["message"].as_str().unwrap().contains("0.9.1"));
+}
+
+#[test]
+fn json_dry_run_reports_a_downgrade() {
+    let repo = TestRepo::new();
+    repo.tag("0.9.0");
+    repo.commit("feat!: the 1.0 api");
+    repo.tag("1.0.0");
+    repo.commit("fix: backport a fix");
+
+    let result = bump_json(&repo, &["-s", "patch", "--dry-run", "--base-tag", "0.9.0"]);
+
+    assert_eq!(result["tag"], "0.9.1");
+    assert_eq!(result["would_create"], false);
+    assert_eq!(result["reason"], "downgrade");
+    assert!(result["message"]
+        .as_str()
+        .unwrap()
+        .contains("'1.0.0' already exists and is not lower than the new version '0.9.1'"));
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+#[test]
+fn bump_keeps_the_leading_v() {
+    let repo = TestRepo::new();
+    repo.tag("v1.2.3");
+    repo.commit("fix: a bug");
+
+    let output = stdout(repo.semtag().args(["bump", "-s", "patch"]));
+
+    assert!(output.contains("'v1.2.4'"), "{}", output);
+    assert!(repo.tag_exists("v1.2.4"));
+    assert!(!repo.tag_exists("1.2.4"));
+}
+
+#[test]
+fn bump_keeps_the_leading_v_after_a_prefix() {
+    let repo = TestRepo::new();
+    repo.tag("prod-v0.9.0");
+    repo.commit("feat: a feature");
+
+    stdout(
+        repo.semtag()
+            .args(["bump", "-p", "prod", "-s", "minor", "-o", "rc"]),
+    );
+
+    assert!(repo.tag_exists("prod-v0.10.0-rc.1"));
+    assert_eq!(
+        stdout(repo.semtag().args(["get", "-p", "prod"])),
+        "prod-v0.10.0-rc.1"
+    );
+}
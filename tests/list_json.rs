@@ -0,0 +1,53 @@
+mod common;
+
+use common::{stdout, TestRepo};
+use serde_json::{json, Value};
+
+#[test]
+fn list_json_describes_lightweight_and_annotated_tags() {
+    let repo = TestRepo::new();
+    let first = repo.commit("feat: first release").to_string();
+    repo.tag("1.0.0");
+    let second = repo.commit("feat: second release").to_string();
+    // 2024-05-01T12:00:00Z
+    repo.tag_at("1.1.0-rc.2", 1_714_564_800);
+
+    let output = stdout(repo.semtag().args(["list", "--json"]));
+    let tags: Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(
+        tags,
+        json!([
+            {
+                "name": "1.0.0",
+                "version": {
+                    "prefix": null,
+                    "major": 1,
+                    "minor": 0,
+                    "patch": 0,
+                    "label": null,
+                    "rc_number": null,
+                    "build": null
+                },
+                "commit": first,
+                "annotated": false,
+                "tagger_date": null
+            },
+            {
+                "name": "1.1.0-rc.2",
+                "version": {
+                    "prefix": null,
+                    "major": 1,
+                    "minor": 1,
+                    "patch": 0,
+                    "label": "rc.2",
+                    "rc_number": 2,
+                    "build": null
+                },
+                "commit": second,
+                "annotated": true,
+                "tagger_date": "2024-05-01T12:00:00Z"
+            }
+        ])
+    );
+}
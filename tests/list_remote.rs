@@ -0,0 +1,80 @@
+mod common;
+
+use common::{stdout, TestRepo};
+use git2::Signature;
+
+/// A bare remote with one commit carrying the given tags, annotated when the flag is set
+fn remote(tags: &[(&str, bool)]) -> TestRepo {
+    let remote = TestRepo::bare();
+    {
+        let repo = &remote.repo;
+        let signature = Signature::now("Semtag Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.treebuilder(None).unwrap().write().unwrap())
+            .unwrap();
+        let commit = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "initial commit",
+                &tree,
+                &[],
+            )
+            .unwrap();
+        let commit = repo.find_object(commit, None).unwrap();
+        for (tag, annotated) in tags {
+            if *annotated {
+                repo.tag(tag, &commit, &signature, tag, false).unwrap();
+            } else {
+                repo.tag_lightweight(tag, &commit, false).unwrap();
+            }
+        }
+    }
+    remote
+}
+
+#[test]
+fn lists_the_remote_tags_missing_locally() {
+    let remote = remote(&[
+        ("1.0.0", false),
+        ("1.1.0", true),
+        ("2.0.0-rc.1", false),
+        ("prod-3.0.0", false),
+        ("not-a-version", false),
+    ]);
+    let local = TestRepo::new();
+    local.tag("1.0.0");
+    local
+        .repo
+        .remote("origin", remote.path().to_str().unwrap())
+        .unwrap();
+
+    let listed = stdout(local.semtag().args(["list", "--list-remote"]));
+    assert_eq!(listed.lines().collect::<Vec<_>>(), ["1.1.0", "2.0.0-rc.1"]);
+
+    let listed = stdout(local.semtag().args(["list", "--list-remote", "-p", "prod"]));
+    assert_eq!(listed, "prod-3.0.0");
+
+    // Nothing was fetched
+    assert!(!local.tag_exists("1.1.0"));
+    assert!(!local.tag_exists("prod-3.0.0"));
+}
+
+#[test]
+fn lists_nothing_when_in_sync() {
+    let remote = remote(&[("1.0.0", true)]);
+    let local = TestRepo::new();
+    local.tag("1.0.0");
+    local
+        .repo
+        .remote("upstream", remote.path().to_str().unwrap())
+        .unwrap();
+
+    let listed = stdout(
+        local
+            .semtag()
+            .args(["list", "--list-remote", "--remote", "upstream"]),
+    );
+    assert_eq!(listed, "");
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+/// Tags created in an order unrelated to their versions, the hotfix 1.4.1 last
+fn repo() -> TestRepo {
+    let repo = TestRepo::new();
+    for (index, tag) in ["1.0.0", "2.0.0", "1.2.0", "2.1.0", "1.4.0", "1.4.1"]
+        .iter()
+        .enumerate()
+    {
+        repo.tag_at(tag, 1_700_000_000 + index as i64 * 60);
+    }
+    repo
+}
+
+#[test]
+fn cap_keeps_the_latest_version_within_it() {
+    let repo = repo();
+
+    let uncapped = stdout(repo.semtag().arg("get"));
+    assert_eq!(uncapped, "2.1.0");
+    for max_tags in ["3", "4", "6", "100"] {
+        let capped = stdout(repo.semtag().args(["get", "--max-tags", max_tags]));
+        assert_eq!(capped, uncapped, "--max-tags {}", max_tags);
+    }
+}
+
+#[test]
+fn cap_only_looks_at_the_most_recent_tags() {
+    let repo = repo();
+
+    assert_eq!(
+        stdout(repo.semtag().args(["get", "--max-tags", "2"])),
+        "1.4.1"
+    );
+}
+
+#[test]
+fn cap_bounds_the_base_of_a_bump() {
+    let repo = repo();
+    repo.commit("fix: a bug");
+
+    let output =
+        stdout(
+            repo.semtag()
+                .args(["bump", "-s", "patch", "--dry-run", "--max-tags", "3"]),
+        );
+    assert!(output.contains("2.1.1"), "{}", output);
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+fn namespaced_repo() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.tag("releases/1.2.0");
+    repo.tag("releases/nightly/9.0.0");
+    repo.tag("5.0.0");
+    repo.commit("feat: a feature");
+    repo
+}
+
+#[test]
+fn namespace_creates_the_tag_under_the_nested_ref() {
+    let repo = namespaced_repo();
+
+    let output = stdout(
+        repo.semtag()
+            .args(["bump", "-s", "minor", "--namespace", "releases"]),
+    );
+
+    assert!(output.contains("'releases/1.3.0'"), "{}", output);
+    assert!(repo.tag_exists("releases/1.3.0"));
+    assert!(!repo.tag_exists("1.3.0"));
+    assert!(!repo.tag_exists("5.1.0"));
+}
+
+#[test]
+fn namespace_lists_only_the_tags_directly_under_it() {
+    let repo = namespaced_repo();
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--namespace", "/releases/"]),
+    );
+
+    let listed = stdout(repo.semtag().args(["list", "--namespace", "releases"]));
+    assert_eq!(listed, "releases/1.2.0\nreleases/1.2.1");
+
+    let latest = stdout(repo.semtag().args(["get", "--namespace", "releases"]));
+    assert_eq!(latest, "releases/1.2.1");
+
+    // Without --namespace the nested tags are not version tags
+    assert_eq!(stdout(repo.semtag().arg("list")), "5.0.0");
+}
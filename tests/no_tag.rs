@@ -0,0 +1,62 @@
+mod common;
+
+use common::{run, stdout, TestRepo};
+
+#[test]
+fn no_tag_skips_the_tag_but_runs_the_rest_of_the_bump() {
+    let repo = TestRepo::new();
+    repo.tag("1.0.0");
+    repo.commit("fix: a bug");
+
+    let output = stdout(repo.semtag().args([
+        "bump",
+        "-s",
+        "patch",
+        "--no-tag",
+        "--create-branch",
+        "release/{major}.{minor}",
+        "--update-version-file",
+        "VERSION",
+    ]));
+
+    assert!(output.contains("Skipped tag '1.0.1'"), "{}", output);
+    assert!(!repo.tag_exists("1.0.1"));
+    assert!(repo
+        .repo
+        .find_branch("release/1.0", git2::BranchType::Local)
+        .is_ok());
+    let version = std::fs::read_to_string(repo.path().join("VERSION")).unwrap();
+    assert_eq!(version.trim_end(), "1.0.1");
+    let lock = std::fs::read_to_string(repo.path().join("semtag.lock")).unwrap();
+    assert!(lock.contains("1.0.1"), "{}", lock);
+}
+
+#[test]
+fn no_tag_fails_like_a_real_run_when_the_tag_exists() {
+    let repo = TestRepo::new();
+    repo.tag("1.0.0");
+    repo.tag("1.0.1");
+    repo.commit("fix: a bug");
+
+    let output =
+        run(repo
+            .semtag()
+            .args(["bump", "-s", "patch", "--no-tag", "--base-tag", "1.0.0"]));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn no_tag_reports_not_created_in_json() {
+    let repo = TestRepo::new();
+    repo.tag("1.0.0");
+    repo.commit("feat: a feature");
+
+    let output = stdout(
+        repo.semtag()
+            .args(["bump", "-s", "minor", "--no-tag", "--format", "json"]),
+    );
+    let result: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["tag"], "1.1.0");
+    assert_eq!(result["created"], false);
+    assert!(!repo.tag_exists("1.1.0"));
+}
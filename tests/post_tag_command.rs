@@ -0,0 +1,62 @@
+mod common;
+
+use common::{run, stdout, TestRepo};
+use std::fs;
+
+const WRITE_TAG: &str = "printf '%s' \"$SEMTAG_NEW_TAG\" > posted.txt";
+
+#[test]
+fn post_tag_command_runs_with_the_new_tag() {
+    let repo = TestRepo::new();
+    repo.tag("0.4.2");
+    repo.commit("feat: a feature");
+
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "minor", "--post-tag-command", WRITE_TAG]),
+    );
+
+    assert!(repo.tag_exists("0.5.0"));
+    let posted = fs::read_to_string(repo.path().join("posted.txt")).unwrap();
+    assert_eq!(posted, "0.5.0");
+}
+
+#[test]
+fn post_tag_command_does_not_run_on_dry_run() {
+    let repo = TestRepo::new();
+    repo.tag("0.4.2");
+    repo.commit("fix: a bug");
+
+    let output = stdout(repo.semtag().args([
+        "bump",
+        "-s",
+        "patch",
+        "--dry-run",
+        "--post-tag-command",
+        WRITE_TAG,
+    ]));
+
+    assert!(
+        output.contains(&format!("Would run     : {}", WRITE_TAG)),
+        "{}",
+        output
+    );
+    assert!(!repo.path().join("posted.txt").exists());
+    assert!(!repo.tag_exists("0.4.3"));
+}
+
+#[test]
+fn post_tag_command_exit_status_is_forwarded() {
+    let repo = TestRepo::new();
+    repo.tag("0.4.2");
+    repo.commit("fix: a bug");
+
+    let output = run(repo
+        .semtag()
+        .args(["bump", "-s", "patch", "--post-tag-command", "exit 7"]));
+
+    assert_eq!(output.status.code(), Some(7));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Post-tag command failed"));
+    // The command runs after the tag, which stays in place
+    assert!(repo.tag_exists("0.4.3"));
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{run, TestRepo};
+
+const NEW_SERIES: &str =
+    "No tags found with prefix 'prod' but unprefixed version tags exist, starting a new 'prod' series from 0.0.0";
+
+#[test]
+fn prefix_without_prefixed_tags_warns_about_a_new_series() {
+    let repo = TestRepo::new();
+    repo.tag("1.4.0");
+    repo.commit("fix: a bug");
+
+    let output = run(repo.semtag().args(["bump", "-p", "prod", "-s", "patch"]));
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("Warning: {}", NEW_SERIES)),
+        "{}",
+        stderr
+    );
+    assert!(repo.tag_exists("prod-0.0.1"));
+}
+
+#[test]
+fn prefix_without_prefixed_tags_is_an_error_under_strict() {
+    let repo = TestRepo::new();
+    repo.tag("1.4.0");
+    repo.commit("fix: a bug");
+
+    let output = run(repo
+        .semtag()
+        .args(["bump", "-p", "prod", "-s", "patch", "--strict"]));
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("Error: {}", NEW_SERIES)),
+        "{}",
+        stderr
+    );
+    assert!(!repo.tag_exists("prod-0.0.1"));
+}
+
+#[test]
+fn prefix_with_prefixed_tags_continues_the_series_silently() {
+    let repo = TestRepo::new();
+    repo.tag("1.4.0");
+    repo.tag("prod-0.3.0");
+    repo.commit("fix: a bug");
+
+    let output = run(repo
+        .semtag()
+        .args(["bump", "-p", "prod", "-s", "patch", "--strict"]));
+
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("new 'prod' series"));
+    assert!(repo.tag_exists("prod-0.3.1"));
+}
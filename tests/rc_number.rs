@@ -0,0 +1,68 @@
+mod common;
+
+use common::{run, stdout, TestRepo};
+
+#[test]
+fn rc_number_starts_a_new_series_at_the_given_number() {
+    let repo = TestRepo::new();
+    repo.tag("1.2.0");
+    repo.commit("feat: a feature");
+
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "minor", "-o", "rc", "--rc-number", "0"]),
+    );
+    assert!(repo.tag_exists("1.3.0-rc.0"));
+
+    repo.commit("fix: a bug");
+    stdout(
+        repo.semtag()
+            .args(["bump", "-o", "rc", "--rc-number", "17"]),
+    );
+    assert!(repo.tag_exists("1.3.0-rc.17"));
+    assert!(!repo.tag_exists("1.3.0-rc.1"));
+}
+
+#[test]
+fn rc_number_not_above_the_existing_rc_warns() {
+    let repo = TestRepo::new();
+    repo.tag("1.3.0-rc.4");
+    repo.commit("fix: a bug");
+
+    let output =
+        run(repo
+            .semtag()
+            .args(["bump", "-o", "rc", "--rc-number", "2", "--allow-downgrade"]));
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Warning: rc.2 is not greater than the existing rc.4 of 1.3.0-rc.4"),
+        "{}",
+        stderr
+    );
+    assert!(repo.tag_exists("1.3.0-rc.2"));
+}
+
+#[test]
+fn rc_number_requires_the_rc_option() {
+    let repo = TestRepo::new();
+    repo.tag("1.0.0");
+
+    let output = run(repo
+        .semtag()
+        .args(["bump", "-s", "patch", "-o", "beta", "--rc-number", "3"]));
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--rc-number requires --option rc"));
+}
+
+#[test]
+fn negative_rc_number_is_rejected() {
+    let repo = TestRepo::new();
+
+    let output = run(repo.semtag().args(["bump", "-o", "rc", "--rc-number=-1"]));
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid value '-1'"));
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+#[test]
+fn git_dir_environment_selects_the_repository() {
+    let hooked = TestRepo::new();
+    hooked.tag("1.0.0");
+    let other = TestRepo::new();
+    other.tag("2.0.0");
+
+    let latest = stdout(
+        other
+            .semtag()
+            .arg("get")
+            .env("GIT_DIR", hooked.path().join(".git")),
+    );
+    assert_eq!(latest, "1.0.0");
+}
+
+#[test]
+fn explicit_path_wins_over_git_dir_environment() {
+    let hooked = TestRepo::new();
+    hooked.tag("1.0.0");
+    let other = TestRepo::new();
+    other.tag("2.0.0");
+
+    let latest = stdout(
+        hooked
+            .semtag()
+            .args(["get", "-C"])
+            .arg(other.path())
+            .env("GIT_DIR", hooked.path().join(".git")),
+    );
+    assert_eq!(latest, "2.0.0");
+}
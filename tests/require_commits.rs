@@ -0,0 +1,36 @@
+mod common;
+
+use common::{run, stdout, TestRepo};
+
+#[test]
+fn require_commits_refuses_when_head_is_the_latest_tag() {
+    let repo = TestRepo::new();
+    repo.commit("feat: first release");
+    repo.tag("3.0.0");
+
+    let output = run(repo
+        .semtag()
+        .args(["bump", "-s", "patch", "--require-commits"]));
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Error: no new commits since '3.0.0', nothing to release"),
+        "{}",
+        stderr
+    );
+    assert!(!repo.tag_exists("3.0.1"));
+}
+
+#[test]
+fn require_commits_allows_a_bump_after_a_new_commit() {
+    let repo = TestRepo::new();
+    repo.tag("3.0.0");
+    repo.commit("fix: a bug");
+
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--require-commits"]),
+    );
+    assert!(repo.tag_exists("3.0.1"));
+}
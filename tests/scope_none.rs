@@ -0,0 +1,33 @@
+mod common;
+
+use common::{run, stdout, TestRepo};
+
+#[test]
+fn scope_none_tags_a_prerelease_of_the_latest_release() {
+    let repo = TestRepo::new();
+    repo.tag("1.2.0");
+    repo.commit("chore: prepare the next round");
+
+    let output = stdout(repo.semtag().args(["bump", "-s", "none", "-o", "alpha"]));
+
+    assert!(output.contains("'1.2.0-alpha'"), "{}", output);
+    assert!(repo.tag_exists("1.2.0-alpha"));
+}
+
+#[test]
+fn option_alone_cannot_move_below_the_latest_release() {
+    let repo = TestRepo::new();
+    repo.tag("1.1.0");
+    repo.commit("fix: a bug");
+    repo.tag("1.2.0");
+    repo.commit("fix: another bug");
+
+    let output = run(repo
+        .semtag()
+        .args(["bump", "-o", "alpha", "--base-tag", "1.1.0"]));
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'1.2.0' already exists"), "{}", stderr);
+    assert!(!repo.tag_exists("1.1.0-alpha"));
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::{stdout, TestRepo};
+
+#[test]
+fn summary_names_the_prefix_versions_and_scope() {
+    let repo = TestRepo::new();
+    repo.tag("prod-1.2.3");
+    repo.commit("feat: a feature");
+
+    let summary = stdout(
+        repo.semtag()
+            .args(["bump", "-p", "prod", "-s", "minor", "--summary"]),
+    );
+
+    assert_eq!(summary, "Bumping prod from 1.2.3 to 1.3.0 (minor)");
+    assert!(!repo.tag_exists("prod-1.3.0"));
+}
+
+#[test]
+fn summary_includes_the_option_and_the_resolved_alias() {
+    let repo = TestRepo::new();
+    repo.tag("0.8.1");
+    repo.commit("feat: a feature");
+
+    let summary = stdout(
+        repo.semtag()
+            .args(["bump", "-s", "feat", "-o", "beta", "--summary"]),
+    );
+
+    assert_eq!(summary, "Bumping from 0.8.1 to 0.9.0-beta (minor, beta)");
+}
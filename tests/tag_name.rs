@@ -0,0 +1,43 @@
+mod common;
+
+use common::{run, TestRepo};
+
+fn assert_rejected(repo: &TestRepo, args: &[&str], tag: &str) {
+    let output = run(repo.semtag().args(args));
+    assert!(!output.status.success(), "{:?} succeeded", args);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("'{}' is not a valid git tag name", tag)),
+        "{}",
+        stderr
+    );
+    assert!(!repo.tag_exists(tag));
+}
+
+#[test]
+fn prefix_with_two_dots_is_rejected() {
+    let repo = TestRepo::new();
+
+    assert_rejected(&repo, &["bump", "-p", "a..b", "-s", "minor"], "a..b-0.1.0");
+    assert_rejected(
+        &repo,
+        &["bump", "-p", "a..b", "-s", "minor", "--dry-run"],
+        "a..b-0.1.0",
+    );
+}
+
+#[test]
+fn name_with_a_space_is_rejected() {
+    let repo = TestRepo::new();
+
+    assert_rejected(
+        &repo,
+        &["bump", "-p", "my app", "-s", "patch"],
+        "my app-0.0.1",
+    );
+    assert_rejected(
+        &repo,
+        &["bump", "-p", "my app", "-s", "patch", "--dry-run"],
+        "my app-0.0.1",
+    );
+}
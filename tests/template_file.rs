@@ -0,0 +1,104 @@
+mod common;
+
+use common::{run, stdout, TestRepo};
+use std::fs;
+
+const TEMPLATE: &str = "Release {version} (was {previous})\nPublished on {date}\n";
+
+fn repo_with_template(template: &str) -> TestRepo {
+    let repo = TestRepo::new();
+    repo.tag("1.4.2");
+    repo.commit("fix: a bug");
+    fs::write(repo.path().join("release.tmpl"), template).unwrap();
+    repo
+}
+
+fn assert_expanded(message: &str) {
+    let (first, date) = message
+        .split_once("\nPublished on ")
+        .unwrap_or_else(|| panic!("unexpected message {:?}", message));
+    assert_eq!(first, "Release 1.4.3 (was 1.4.2)");
+    let parts: Vec<&str> = date.split('-').collect();
+    assert_eq!(
+        parts.iter().map(|part| part.len()).collect::<Vec<_>>(),
+        [4, 2, 2],
+        "{:?}",
+        date
+    );
+    assert!(parts.concat().chars().all(|c| c.is_ascii_digit()));
+}
+
+#[test]
+fn template_file_becomes_the_annotated_tag_message() {
+    let repo = repo_with_template(TEMPLATE);
+
+    stdout(
+        repo.semtag()
+            .args(["bump", "-s", "patch", "--template-file", "release.tmpl"]),
+    );
+
+    let tag = repo
+        .repo
+        .find_reference("refs/tags/1.4.3")
+        .unwrap()
+        .peel_to_tag()
+        .expect("--template-file creates an annotated tag");
+    assert_expanded(tag.message().unwrap().trim_end());
+}
+
+#[test]
+fn dry_run_prints_the_expanded_template() {
+    let repo = repo_with_template(TEMPLATE);
+
+    let output = stdout(repo.semtag().args([
+        "bump",
+        "-s",
+        "patch",
+        "--template-file",
+        "release.tmpl",
+        "--dry-run",
+    ]));
+
+    let message = output
+        .split_once("Tag message   :\n")
+        .and_then(|(_, rest)| rest.split_once("\nWould update"))
+        .map(|(message, _)| message)
+        .unwrap_or_else(|| panic!("no tag message in {}", output));
+    assert_expanded(message);
+    assert!(!repo.tag_exists("1.4.3"));
+}
+
+#[test]
+fn missing_template_file_is_an_error() {
+    let repo = repo_with_template(TEMPLATE);
+
+    let output =
+        run(repo
+            .semtag()
+            .args(["bump", "-s", "patch", "--template-file", "missing.tmpl"]));
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Error reading template file"), "{}", stderr);
+    assert!(stderr.contains("missing.tmpl"), "{}", stderr);
+    assert!(!repo.tag_exists("1.4.3"));
+}
+
+#[test]
+fn unknown_placeholder_is_an_error() {
+    let repo = repo_with_template("Release {verison}\n");
+
+    let output =
+        run(repo
+            .semtag()
+            .args(["bump", "-s", "patch", "--template-file", "release.tmpl"]));
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unknown placeholder '{verison}'"),
+        "{}",
+        stderr
+    );
+    assert!(!repo.tag_exists("1.4.3"));
+}
@@ -0,0 +1,88 @@
+use proptest::prelude::*;
+use semtag::Version;
+use std::cmp::Ordering;
+
+/// A pre-release identifier, alphanumeric or numeric without leading zeros
+fn identifier() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z][a-z0-9]{0,5}",
+        (0u32..1000).prop_map(|number| number.to_string()),
+    ]
+}
+
+fn label() -> impl Strategy<Value = String> {
+    prop::collection::vec(identifier(), 1..4).prop_map(|identifiers| identifiers.join("."))
+}
+
+/// A version string as semtag renders it, `[prefix-]major.minor.patch[-label][+build]`
+fn version_string() -> impl Strategy<Value = String> {
+    (
+        prop::option::of("[a-z]{1,5}(-[a-z]{1,4})?"),
+        0u32..100_000,
+        0u32..100_000,
+        0u32..100_000,
+        prop::option::of(label()),
+        prop::option::of("[0-9A-Za-z]{1,6}(\\.[0-9A-Za-z]{1,6}){0,2}"),
+    )
+        .prop_map(|(prefix, major, minor, patch, label, build)| {
+            let mut version = format!("{}.{}.{}", major, minor, patch);
+            if let Some(prefix) = prefix {
+                version = format!("{}-{}", prefix, version);
+            }
+            if let Some(label) = label {
+                version = format!("{}-{}", version, label);
+            }
+            if let Some(build) = build {
+                version = format!("{}+{}", version, build);
+            }
+            version
+        })
+}
+
+/// Versions that parse, the few labels semtag rejects on purpose such as `rc.abc` are skipped
+fn version() -> impl Strategy<Value = Version> {
+    version_string().prop_filter_map("rejected by Version::parse", |version| {
+        Version::parse(&version).ok()
+    })
+}
+
+proptest! {
+    #[test]
+    fn display_round_trips_through_parse(version in version_string()) {
+        if let Ok(parsed) = Version::parse(&version) {
+            prop_assert_eq!(parsed.to_string(), version.clone());
+            let reparsed = Version::parse(&parsed.to_string()).unwrap();
+            prop_assert_eq!(reparsed.to_string(), version);
+            prop_assert_eq!(reparsed.cmp(&parsed), Ordering::Equal);
+        }
+    }
+
+    #[test]
+    fn ordering_is_antisymmetric(a in version(), b in version()) {
+        prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        prop_assert_eq!(a == b, a.cmp(&b) == Ordering::Equal);
+    }
+
+    #[test]
+    fn ordering_is_transitive(a in version(), b in version(), c in version()) {
+        if a <= b && b <= c {
+            prop_assert!(a <= c);
+        }
+        if a >= b && b >= c {
+            prop_assert!(a >= c);
+        }
+    }
+
+    #[test]
+    fn ordering_matches_semver_precedence(a in version(), b in version()) {
+        let (a, b) = (a.without_prefix(), b.without_prefix());
+        let expected = a.to_semver().unwrap().cmp_precedence(&b.to_semver().unwrap());
+        prop_assert_eq!(a.cmp(&b), expected);
+    }
+
+    #[test]
+    fn build_metadata_does_not_affect_ordering(version in version()) {
+        let core = Version { build: None, ..version.clone() };
+        prop_assert_eq!(version.cmp(&core), Ordering::Equal);
+    }
+}